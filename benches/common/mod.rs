@@ -0,0 +1,11 @@
+/// Returns `len` pseudo-random numbers from a xorshift generator started at `state`.
+pub fn random(len: usize, mut state: u64) -> Vec<u64> {
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        })
+        .collect()
+}
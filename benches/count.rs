@@ -0,0 +1,39 @@
+#![feature(test)]
+
+extern crate test;
+
+use signed::region;
+use test::{black_box, Bencher};
+
+fn haystack() -> Vec<u8> {
+    (0..64 * 1024u32).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+#[bench]
+fn count_byte(b: &mut Bencher) {
+    let haystack = haystack();
+
+    region(haystack.as_slice(), |s| {
+        b.iter(|| {
+            (0..=255u8)
+                .map(|byte| s.count_byte(s.range(), black_box(byte)))
+                .sum::<usize>()
+        })
+    });
+}
+
+#[bench]
+fn count_iterator(b: &mut Bencher) {
+    let haystack = haystack();
+
+    region(haystack.as_slice(), |s| {
+        b.iter(|| {
+            (0..=255u8)
+                .map(|byte| {
+                    let byte = black_box(byte);
+                    s.count(s.range(), |&x| x == byte)
+                })
+                .sum::<usize>()
+        })
+    });
+}
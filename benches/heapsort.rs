@@ -0,0 +1,61 @@
+#![feature(test)]
+
+extern crate test;
+
+mod common;
+
+use signed::{algorithms::heapsort, region};
+use test::Bencher;
+
+/// Heapsort on a plain slice, where every access is bounds checked.
+fn heapsort_checked(v: &mut [u64]) {
+    fn sift_down(v: &mut [u64], mut node: usize, end: usize) {
+        loop {
+            let mut child = 2 * node + 1;
+            if child >= end {
+                return;
+            }
+            if child + 1 < end && v[child] < v[child + 1] {
+                child += 1;
+            }
+            if v[node] >= v[child] {
+                return;
+            }
+            v.swap(node, child);
+            node = child;
+        }
+    }
+
+    for node in (0..v.len() / 2).rev() {
+        sift_down(v, node, v.len());
+    }
+    for end in (1..v.len()).rev() {
+        v.swap(0, end);
+        sift_down(v, 0, end);
+    }
+}
+
+#[bench]
+fn heapsort_branded(b: &mut Bencher) {
+    let data = common::random(100_000, 0x9e37_79b9_7f4a_7c15);
+
+    b.iter(|| {
+        let mut v = data.clone();
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range();
+            heapsort(&mut s, r);
+        });
+        v
+    });
+}
+
+#[bench]
+fn heapsort_bounds_checked(b: &mut Bencher) {
+    let data = common::random(100_000, 0x9e37_79b9_7f4a_7c15);
+
+    b.iter(|| {
+        let mut v = data.clone();
+        heapsort_checked(&mut v);
+        v
+    });
+}
@@ -0,0 +1,33 @@
+#![feature(test)]
+
+extern crate test;
+
+mod common;
+
+use signed::{algorithms::quicksort, region};
+use test::Bencher;
+
+#[bench]
+fn quicksort_random_u64(b: &mut Bencher) {
+    let data = common::random(100_000, 0x2545_f491_4f6c_dd1d);
+
+    b.iter(|| {
+        let mut v = data.clone();
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range();
+            quicksort(&mut s, r);
+        });
+        v
+    });
+}
+
+#[bench]
+fn sort_unstable_random_u64(b: &mut Bencher) {
+    let data = common::random(100_000, 0x2545_f491_4f6c_dd1d);
+
+    b.iter(|| {
+        let mut v = data.clone();
+        v.sort_unstable();
+        v
+    });
+}
@@ -0,0 +1,368 @@
+//! Derive macros for the container traits of `signed`.
+//!
+//! This crate is re-exported by `signed` behind the `derive` feature,
+//! and should not be depended upon directly.
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+/// Derives forwarding impls of the container traits
+/// (`ContainerTrait`, `Contiguous`, `ContiguousMut`, `GetUnchecked`, `GetUncheckedMut`,
+/// `SplitUnchecked` and `SplitUncheckedMut`) for a struct with a single field.
+///
+/// Each trait is only implemented if the field implements it.
+#[proc_macro_derive(Container)]
+pub fn derive_container(input: TokenStream) -> TokenStream {
+    match Input::parse(input) {
+        Ok(input) => input.expand().parse().unwrap(),
+        Err(message) => format!("compile_error!({:?});", message).parse().unwrap(),
+    }
+}
+
+/// The parts of a struct definition the derive needs.
+struct Input {
+    name: String,
+    impl_generics: String,
+    ty_generics: String,
+    where_clause: Vec<String>,
+    field: String,
+    field_ty: String,
+}
+
+impl Input {
+    fn parse(input: TokenStream) -> Result<Self, String> {
+        let mut tokens = input.into_iter().peekable();
+
+        // Skip outer attributes and the visibility.
+        loop {
+            match tokens.peek() {
+                Some(TokenTree::Punct(p)) if p.as_char() == '#' => {
+                    tokens.next();
+                    tokens.next();
+                }
+                Some(TokenTree::Ident(i)) if i.to_string() == "pub" => {
+                    tokens.next();
+                    if let Some(TokenTree::Group(g)) = tokens.peek() {
+                        if g.delimiter() == Delimiter::Parenthesis {
+                            tokens.next();
+                        }
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        match tokens.next() {
+            Some(TokenTree::Ident(i)) if i.to_string() == "struct" => {}
+            Some(TokenTree::Ident(i)) => {
+                return Err(format!(
+                    "`#[derive(Container)]` can only be used on structs, not on {}s",
+                    i
+                ))
+            }
+            _ => return Err("`#[derive(Container)]` expected a struct".to_string()),
+        }
+
+        let name = match tokens.next() {
+            Some(TokenTree::Ident(i)) => i.to_string(),
+            _ => return Err("`#[derive(Container)]` expected a struct name".to_string()),
+        };
+
+        let mut generics = vec![];
+        if let Some(TokenTree::Punct(p)) = tokens.peek() {
+            if p.as_char() == '<' {
+                tokens.next();
+
+                let mut depth = 0;
+                let mut previous = None;
+                for token in &mut tokens {
+                    match &token {
+                        TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+                        // As in `split_commas`, the arrow of a function signature does not close.
+                        TokenTree::Punct(p) if p.as_char() == '>' && previous != Some('-') => {
+                            if depth == 0 {
+                                break;
+                            }
+                            depth -= 1;
+                        }
+                        _ => {}
+                    }
+                    previous = match &token {
+                        TokenTree::Punct(p) => Some(p.as_char()),
+                        _ => None,
+                    };
+                    generics.push(token);
+                }
+            }
+        }
+
+        let mut where_tokens = vec![];
+        let mut in_where = false;
+        let mut body = None;
+        for token in tokens {
+            match token {
+                // Parentheses within a where clause are part of a bound, not a tuple struct body.
+                TokenTree::Group(g)
+                    if body.is_none()
+                        && (g.delimiter() == Delimiter::Brace
+                            || (g.delimiter() == Delimiter::Parenthesis && !in_where)) =>
+                {
+                    body = Some(g)
+                }
+                TokenTree::Punct(p) if p.as_char() == ';' => break,
+                TokenTree::Ident(i) if i.to_string() == "where" => in_where = true,
+                other => where_tokens.push(other),
+            }
+        }
+
+        let body = body.ok_or("`#[derive(Container)]` can not be used on unit structs")?;
+        let named = body.delimiter() == Delimiter::Brace;
+
+        let mut fields = split_commas(body.stream().into_iter().collect());
+        fields.retain(|field| !field.is_empty());
+
+        if fields.len() != 1 {
+            return Err(format!(
+                "`#[derive(Container)]` requires a struct with exactly one field, `{}` has {}",
+                name,
+                fields.len()
+            ));
+        }
+
+        let mut field = fields.pop().unwrap();
+        strip_attributes_and_visibility(&mut field);
+
+        let (field, field_ty) = if named {
+            let ident = field[0].to_string();
+            (ident, tokens_to_string(&field[2..]))
+        } else {
+            ("0".to_string(), tokens_to_string(&field))
+        };
+
+        let mut impl_generics = vec![];
+        let mut ty_generics = vec![];
+        for param in split_commas(generics) {
+            if param.is_empty() {
+                continue;
+            }
+
+            let param = match param.iter().position(|t| is_punct(t, '=')) {
+                Some(default) => param[..default].to_vec(),
+                None => param,
+            };
+
+            let name = match &param[0] {
+                TokenTree::Punct(p) if p.as_char() == '\'' => tokens_to_string(&param[..2]),
+                TokenTree::Ident(i) if i.to_string() == "const" => param[1].to_string(),
+                other => other.to_string(),
+            };
+
+            impl_generics.push(tokens_to_string(&param));
+            ty_generics.push(name);
+        }
+
+        let mut where_clause = split_commas(where_tokens)
+            .iter()
+            .filter(|predicate| !predicate.is_empty())
+            .map(|predicate| tokens_to_string(predicate))
+            .collect::<Vec<_>>();
+        where_clause.retain(|predicate| !predicate.is_empty());
+
+        Ok(Self {
+            name,
+            impl_generics: impl_generics.join(", "),
+            ty_generics: ty_generics.join(", "),
+            where_clause,
+            field,
+            field_ty,
+        })
+    }
+
+    /// Returns the header of an impl of `signed::container::traits::$trait`,
+    /// which only applies if the field implements `$trait` as well.
+    fn header(&self, trait_: &str) -> String {
+        let mut predicates = self.where_clause.clone();
+
+        // The higher ranked bound keeps the predicate from being a trivial bound,
+        // such that the impl is simply not applicable if the field does not implement the trait.
+        predicates.push(format!(
+            "for<'__signed> {}: ::signed::container::traits::{}",
+            self.field_ty, trait_
+        ));
+
+        format!(
+            "unsafe impl<{}> ::signed::container::traits::{} for {}<{}> where {}",
+            self.impl_generics,
+            trait_,
+            self.name,
+            self.ty_generics,
+            predicates.join(", ")
+        )
+    }
+
+    fn expand(&self) -> String {
+        let (ty, field) = (&self.field_ty, &self.field);
+        let path = "::signed::container::traits";
+
+        let mut out = String::new();
+
+        out += &format!(
+            "{header} {{
+                type Item = <{ty} as {path}::ContainerTrait>::Item;
+
+                #[inline(always)]
+                fn base_len(&self) -> usize {{
+                    {path}::ContainerTrait::base_len(&self.{field})
+                }}
+            }}",
+            header = self.header("ContainerTrait"),
+        );
+
+        out += &format!(
+            "{header} {{
+                #[inline(always)]
+                fn begin(&self) -> *const Self::Item {{
+                    {path}::Contiguous::begin(&self.{field})
+                }}
+
+                #[inline(always)]
+                fn end(&self) -> *const Self::Item {{
+                    {path}::Contiguous::end(&self.{field})
+                }}
+
+                #[inline(always)]
+                fn as_slice(&self) -> &[Self::Item] {{
+                    {path}::Contiguous::as_slice(&self.{field})
+                }}
+            }}",
+            header = self.header("Contiguous"),
+        );
+
+        out += &format!(
+            "{header} {{
+                #[inline(always)]
+                fn begin_mut(&mut self) -> *mut Self::Item {{
+                    {path}::ContiguousMut::begin_mut(&mut self.{field})
+                }}
+
+                #[inline(always)]
+                fn end_mut(&mut self) -> *mut Self::Item {{
+                    {path}::ContiguousMut::end_mut(&mut self.{field})
+                }}
+
+                #[inline(always)]
+                fn as_mut_slice(&mut self) -> &mut [Self::Item] {{
+                    {path}::ContiguousMut::as_mut_slice(&mut self.{field})
+                }}
+            }}",
+            header = self.header("ContiguousMut"),
+        );
+
+        out += &format!(
+            "{header} {{
+                #[inline(always)]
+                unsafe fn unchecked(&self, index: usize) -> &Self::Item {{
+                    {path}::GetUnchecked::unchecked(&self.{field}, index)
+                }}
+            }}",
+            header = self.header("GetUnchecked"),
+        );
+
+        out += &format!(
+            "{header} {{
+                #[inline(always)]
+                unsafe fn unchecked_mut(&mut self, index: usize) -> &mut Self::Item {{
+                    {path}::GetUncheckedMut::unchecked_mut(&mut self.{field}, index)
+                }}
+            }}",
+            header = self.header("GetUncheckedMut"),
+        );
+
+        out += &format!(
+            "{header} {{
+                type Split = <{ty} as {path}::SplitUnchecked>::Split;
+
+                #[inline(always)]
+                unsafe fn split_unchecked(&self, index: usize) -> (&Self::Split, &Self::Split) {{
+                    {path}::SplitUnchecked::split_unchecked(&self.{field}, index)
+                }}
+            }}",
+            header = self.header("SplitUnchecked"),
+        );
+
+        out += &format!(
+            "{header} {{
+                #[inline(always)]
+                unsafe fn split_unchecked_mut(
+                    &mut self,
+                    index: usize,
+                ) -> (&mut Self::Split, &mut Self::Split) {{
+                    {path}::SplitUncheckedMut::split_unchecked_mut(&mut self.{field}, index)
+                }}
+            }}",
+            header = self.header("SplitUncheckedMut"),
+        );
+
+        out
+    }
+}
+
+fn is_punct(token: &TokenTree, c: char) -> bool {
+    match token {
+        TokenTree::Punct(p) => p.as_char() == c,
+        _ => false,
+    }
+}
+
+fn tokens_to_string(tokens: &[TokenTree]) -> String {
+    tokens.iter().cloned().collect::<TokenStream>().to_string()
+}
+
+/// Splits `tokens` on the commas that are not nested within angle brackets.
+fn split_commas(tokens: Vec<TokenTree>) -> Vec<Vec<TokenTree>> {
+    let mut out = vec![vec![]];
+    let mut depth = 0usize;
+    let mut previous = None;
+
+    for token in tokens {
+        match &token {
+            TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+            // Do not mistake the arrow of a function signature for a closing angle bracket.
+            TokenTree::Punct(p) if p.as_char() == '>' && previous != Some('-') => {
+                depth = depth.saturating_sub(1)
+            }
+            TokenTree::Punct(p) if p.as_char() == ',' && depth == 0 => {
+                out.push(vec![]);
+                previous = Some(',');
+                continue;
+            }
+            _ => {}
+        }
+
+        previous = match &token {
+            TokenTree::Punct(p) => Some(p.as_char()),
+            _ => None,
+        };
+        out.last_mut().unwrap().push(token);
+    }
+
+    out
+}
+
+/// Removes the attributes and visibility in front of a field.
+fn strip_attributes_and_visibility(field: &mut Vec<TokenTree>) {
+    loop {
+        match field.first() {
+            Some(t) if is_punct(t, '#') => {
+                field.drain(..2);
+            }
+            Some(TokenTree::Ident(i)) if i.to_string() == "pub" => {
+                field.remove(0);
+                if let Some(TokenTree::Group(g)) = field.first() {
+                    if g.delimiter() == Delimiter::Parenthesis {
+                        field.remove(0);
+                    }
+                }
+            }
+            _ => break,
+        }
+    }
+}
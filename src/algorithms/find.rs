@@ -0,0 +1,202 @@
+use core::{iter::FusedIterator, marker::PhantomData};
+
+use crate::{
+    container::{container::IndexOf, traits::Contiguous, Container},
+    core::{proof::NonEmpty, range::Range, seal::Contract},
+};
+
+/// Returns an iterator over the ranges of every match of `needle` within `r`, in order.
+///
+/// Matches may overlap: searching for `aa` in `aaaa` yields three matches.
+/// An empty `needle` matches nothing. The search is Knuth-Morris-Pratt,
+/// which runs in `O(n + m)` time, and skips ahead with `memchr` for bytes.
+///
+/// ```
+/// use signed::{algorithms::find_all, region};
+///
+/// region(&b"to be or not to be"[..], |s| {
+///     let starts: Vec<usize> = find_all(&s, s.range(), b"be").map(|m| m.start()).collect();
+///     assert_eq!(starts, [3, 16]);
+/// });
+/// ```
+#[inline]
+pub fn find_all<'a, C, A, T, P>(
+    c: &'a Container<C, A>,
+    r: Range<C, P>,
+    needle: &'a [T],
+) -> FindAll<'a, C, T>
+where
+    C: for<'s> Contract<'s>,
+    A: Contiguous<Item = T>,
+    T: PartialEq,
+{
+    FindAll {
+        haystack: &c[r],
+        offset: r.start(),
+        needle,
+        borders: borders(needle),
+        position: 0,
+        matched: 0,
+        marker: PhantomData,
+    }
+}
+
+/// Returns the length of the longest proper prefix of `needle[..=i]`
+/// that is also a suffix of it, for every `i`.
+fn borders<T: PartialEq>(needle: &[T]) -> Vec<usize> {
+    let mut borders = vec![0; needle.len()];
+    let mut len = 0;
+
+    for i in 1..needle.len() {
+        while len > 0 && needle[i] != needle[len] {
+            len = borders[len - 1];
+        }
+
+        if needle[i] == needle[len] {
+            len += 1;
+        }
+
+        borders[i] = len;
+    }
+
+    borders
+}
+
+/// An iterator over the ranges of every match of a needle within a range.
+///
+/// This struct is created by [`find_all`].
+pub struct FindAll<'a, C, T> {
+    haystack: &'a [T],
+    offset: usize,
+    needle: &'a [T],
+    borders: Vec<usize>,
+    position: usize,
+    matched: usize,
+    marker: PhantomData<C>,
+}
+
+impl<'a, C: for<'s> Contract<'s>, T: PartialEq> Iterator for FindAll<'a, C, T> {
+    type Item = Range<C, NonEmpty>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.needle.first()?;
+
+        while self.position < self.haystack.len() {
+            // Without a partial match, the next match can only start at the next `first`.
+            if self.matched == 0 {
+                match IndexOf::index_of(&self.haystack[self.position..], first) {
+                    Some(skip) => self.position += skip,
+                    None => {
+                        self.position = self.haystack.len();
+                        return None;
+                    }
+                }
+            }
+
+            let item = &self.haystack[self.position];
+
+            while self.matched > 0 && *item != self.needle[self.matched] {
+                self.matched = self.borders[self.matched - 1];
+            }
+
+            if *item == self.needle[self.matched] {
+                self.matched += 1;
+            }
+
+            self.position += 1;
+
+            if self.matched == self.needle.len() {
+                self.matched = self.borders[self.matched - 1];
+
+                let end = self.offset + self.position;
+
+                // The match is a non-empty window of `haystack`, the slice of a range starting at `offset`.
+                return unsafe { Some(Range::from_nonempty(end - self.needle.len(), end)) };
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.haystack.len() - self.position + self.matched;
+
+        match self.needle.len() {
+            0 => (0, Some(0)),
+            len => (0, Some((left + 1).saturating_sub(len))),
+        }
+    }
+}
+
+impl<'a, C: for<'s> Contract<'s>, T: PartialEq> FusedIterator for FindAll<'a, C, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{region, test_util::xorshift};
+
+    fn starts<T: PartialEq>(haystack: &[T], needle: &[T]) -> Vec<usize> {
+        region(haystack, |s| {
+            find_all(&s, s.range(), needle).map(|m| m.start()).collect()
+        })
+    }
+
+    fn naive<T: PartialEq>(haystack: &[T], needle: &[T]) -> Vec<usize> {
+        if needle.is_empty() {
+            return Vec::new();
+        }
+
+        haystack
+            .windows(needle.len())
+            .enumerate()
+            .filter(|(_, window)| *window == needle)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    #[test]
+    fn find_all_overlapping() {
+        assert_eq!(starts(b"aaaa", b"aa"), [0, 1, 2]);
+        assert_eq!(starts(b"abababa", b"aba"), [0, 2, 4]);
+        assert_eq!(starts(b"abc", b""), []);
+        assert_eq!(starts(b"", b"a"), []);
+        assert_eq!(starts(b"ab", b"abc"), []);
+    }
+
+    #[test]
+    fn find_all_matches_naive() {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = || (xorshift(&mut state) % 3) as u8;
+
+        let haystack: Vec<u8> = (0..2000).map(|_| next()).collect();
+
+        for len in 1..8 {
+            let needle: Vec<u8> = (0..len).map(|_| next()).collect();
+            assert_eq!(starts(&haystack, &needle), naive(&haystack, &needle));
+
+            // The same search without the byte fast path.
+            let haystack: Vec<u32> = haystack.iter().map(|&b| b as u32).collect();
+            let needle: Vec<u32> = needle.iter().map(|&b| b as u32).collect();
+            assert_eq!(starts(&haystack, &needle), naive(&haystack, &needle));
+        }
+    }
+
+    #[test]
+    fn find_all_subrange() {
+        let text = b"needle in a haystack, needle in a needle";
+
+        region(&text[..], |s| {
+            let r = s.vet_range(1, 34).unwrap();
+            let matches: Vec<_> = find_all(&s, r, b"needle").collect();
+
+            assert_eq!(matches.len(), 1);
+            assert_eq!(matches[0].start(), 22);
+            assert_eq!(s[matches[0]], *b"needle");
+
+            let (before, after) = r.split_index(matches[0].first());
+            assert_eq!(s[before], *b"eedle in a haystack, ");
+            assert_eq!(s[after], *b"needle in a ");
+        });
+    }
+}
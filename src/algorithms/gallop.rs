@@ -0,0 +1,181 @@
+use core::cmp::Ordering;
+
+use crate::{
+    container::{traits::GetUnchecked, Container},
+    core::{index::Index, proof::Unknown, range::Range, seal::Contract},
+};
+
+/// Returns the first edge within the sorted range `r` at which `x` could be inserted
+/// while keeping `r` sorted, that is, the edge before the first element not less than `x`.
+///
+/// Unlike [`Container::lower_bound`], the search starts at `r.start()` and probes
+/// exponentially growing offsets before narrowing down with a binary search,
+/// so it runs in `O(log k)`, where `k` is the distance from `r.start()` to the answer.
+#[inline]
+pub fn gallop_left<C, A, T, P>(c: &Container<C, A>, r: Range<C, P>, x: &T) -> Index<C, Unknown>
+where
+    C: for<'s> Contract<'s>,
+    A: GetUnchecked<Item = T>,
+    T: Ord,
+{
+    gallop_left_by(c, r, |item| item.cmp(x))
+}
+
+/// Returns the first edge within the range `r` before the first element
+/// for which `f` does not return [`Ordering::Less`].
+///
+/// `f` returns the ordering of an element relative to the target, as in [`Container::binary_search_by`].
+#[inline]
+pub fn gallop_left_by<C, A, T, P, F>(
+    c: &Container<C, A>,
+    r: Range<C, P>,
+    mut f: F,
+) -> Index<C, Unknown>
+where
+    C: for<'s> Contract<'s>,
+    A: GetUnchecked<Item = T>,
+    F: FnMut(&T) -> Ordering,
+{
+    gallop(c, r, |item| f(item) == Ordering::Less)
+}
+
+/// Returns the last edge within the sorted range `r` at which `x` could be inserted
+/// while keeping `r` sorted, that is, the edge before the first element greater than `x`.
+///
+/// Unlike [`Container::upper_bound`], the search starts at `r.start()` and probes
+/// exponentially growing offsets before narrowing down with a binary search,
+/// so it runs in `O(log k)`, where `k` is the distance from `r.start()` to the answer.
+#[inline]
+pub fn gallop_right<C, A, T, P>(c: &Container<C, A>, r: Range<C, P>, x: &T) -> Index<C, Unknown>
+where
+    C: for<'s> Contract<'s>,
+    A: GetUnchecked<Item = T>,
+    T: Ord,
+{
+    gallop_right_by(c, r, |item| item.cmp(x))
+}
+
+/// Returns the first edge within the range `r` before the first element
+/// for which `f` returns [`Ordering::Greater`].
+///
+/// `f` returns the ordering of an element relative to the target, as in [`Container::binary_search_by`].
+#[inline]
+pub fn gallop_right_by<C, A, T, P, F>(
+    c: &Container<C, A>,
+    r: Range<C, P>,
+    mut f: F,
+) -> Index<C, Unknown>
+where
+    C: for<'s> Contract<'s>,
+    A: GetUnchecked<Item = T>,
+    F: FnMut(&T) -> Ordering,
+{
+    gallop(c, r, |item| f(item) != Ordering::Greater)
+}
+
+/// Returns the edge within `r` at which `pred` flips from `true` to `false`,
+/// assuming `r` is partitioned by `pred`.
+///
+/// The elements at offsets 0, 1, 3, 7, 15, ... from the start of `r` are probed
+/// until one fails `pred` or the offset leaves `r`, after which the window
+/// in between the last two probes is binary searched.
+fn gallop<C, A, T, P, F>(c: &Container<C, A>, r: Range<C, P>, mut pred: F) -> Index<C, Unknown>
+where
+    C: for<'s> Contract<'s>,
+    A: GetUnchecked<Item = T>,
+    F: FnMut(&T) -> bool,
+{
+    let r = match r.nonempty() {
+        Some(r) => r,
+        // The start of a range is always an edge of the container.
+        None => return unsafe { Index::new(r.start()) },
+    };
+
+    // Every element before `lo` satisfies `pred`.
+    let mut lo = r.start();
+    let mut probe = r.start();
+    let mut step = 1usize;
+
+    let hi = loop {
+        match r.contains(probe) {
+            Some(index) if pred(&c[index]) => {
+                lo = probe + 1;
+                probe = probe.saturating_add(step);
+                step = step.saturating_mul(2);
+            }
+            Some(index) => break index.integer(),
+            None => break r.end(),
+        }
+    };
+
+    // `lo` is at most one past the last probe within `r`, and `hi` is either
+    // a later probe within `r` or the end of `r`, so `lo..hi` lies within `r`.
+    let window = unsafe { Range::<C>::from_unknown(lo, hi) };
+    c.partition_point(window, pred)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::region;
+
+    /// Gallops over every element of `v` with every possible target,
+    /// and compares the answers with a linear scan.
+    fn check(v: &[u32]) {
+        region(v, |s| {
+            for x in 0..=v.last().map_or(0, |&last| last + 1) {
+                let left = v.iter().take_while(|&&item| item < x).count();
+                let right = v.iter().take_while(|&&item| item <= x).count();
+
+                assert_eq!(gallop_left(&s, s.range(), &x).integer(), left);
+                assert_eq!(gallop_right(&s, s.range(), &x).integer(), right);
+            }
+        });
+    }
+
+    #[test]
+    fn gallop_matches_scan() {
+        check(&[]);
+        check(&[3]);
+        check(&[1, 2, 2, 2, 5, 8, 8, 13, 21, 21, 21, 21, 34]);
+        check(&(0..100).map(|x| x / 3).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn gallop_first_and_last() {
+        let v: Vec<u32> = (10..50).collect();
+
+        region(v.as_slice(), |s| {
+            let r = s.range();
+
+            assert_eq!(gallop_left(&s, r, &0).integer(), 0);
+            assert_eq!(gallop_right(&s, r, &10).integer(), 1);
+            assert_eq!(gallop_left(&s, r, &49).integer(), 39);
+            assert_eq!(gallop_right(&s, r, &49).integer(), 40);
+            assert_eq!(gallop_left(&s, r, &100).integer(), 40);
+        });
+    }
+
+    #[test]
+    fn gallop_stays_within_range() {
+        const OUTSIDE: u32 = u32::MAX;
+
+        // The range holds 7 elements, so the probe at offset 7 would land on the element past it.
+        let v = [OUTSIDE, 1, 2, 3, 4, 5, 6, 7, OUTSIDE];
+
+        region(&v[..], |s| {
+            let r = s.vet_range(1, 8).unwrap();
+            let cmp = |x: u32| {
+                move |item: &u32| {
+                    assert_ne!(*item, OUTSIDE, "probed outside of the range");
+                    item.cmp(&x)
+                }
+            };
+
+            assert_eq!(gallop_left_by(&s, r, cmp(0)).integer(), 1);
+            assert_eq!(gallop_left_by(&s, r, cmp(7)).integer(), 7);
+            assert_eq!(gallop_right_by(&s, r, cmp(7)).integer(), 8);
+            assert_eq!(gallop_right_by(&s, r, cmp(100)).integer(), 8);
+        });
+    }
+}
@@ -0,0 +1,446 @@
+use core::cmp::Ordering;
+
+use crate::{
+    container::{traits::GetUncheckedMut, Container},
+    core::{index::Index, range::Range, seal::Contract},
+};
+
+/// Sorts the elements within `r` with heapsort.
+///
+/// This sort is unstable, does not allocate, and runs in `O(n log n)` in the worst case.
+#[inline]
+pub fn heapsort<C, A, T, P>(c: &mut Container<C, A>, r: Range<C, P>)
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    T: Ord,
+{
+    heapsort_by(c, r, T::cmp)
+}
+
+/// Sorts the elements within `r` with heapsort, using the comparator function `cmp`.
+///
+/// This sort is unstable, does not allocate, and runs in `O(n log n)` in the worst case.
+pub fn heapsort_by<C, A, T, P, F>(c: &mut Container<C, A>, r: Range<C, P>, mut cmp: F)
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if let Some(r) = r.nonempty() {
+        c.heapsort(r, &mut cmp);
+    }
+}
+
+/// Restores the max-heap rooted at the start of `r`,
+/// after the element at `root` may have become smaller than its children.
+///
+/// The children of the element at offset `i` from the start of `r`
+/// are at offsets `2 * i + 1` and `2 * i + 2`.
+///
+/// # Panics
+/// Panics if `root` is not contained within `r`.
+#[inline]
+pub fn sift_down<C, A, T, P>(c: &mut Container<C, A>, r: Range<C, P>, root: Index<C>)
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    T: Ord,
+{
+    sift_down_by(c, r, root, T::cmp)
+}
+
+/// Restores the max-heap rooted at the start of `r`, using the comparator function `cmp`,
+/// after the element at `root` may have become smaller than its children.
+///
+/// # Panics
+/// Panics if `root` is not contained within `r`.
+pub fn sift_down_by<C, A, T, P, F>(
+    c: &mut Container<C, A>,
+    r: Range<C, P>,
+    root: Index<C>,
+    mut cmp: F,
+) where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    assert!(
+        r.start() <= root.integer() && root.integer() < r.end(),
+        "sift_down: index {} is not within {}..{}",
+        root.integer(),
+        r.start(),
+        r.end()
+    );
+
+    // `root` lies within `r`, so it is not empty.
+    c.sift_down(unsafe { r.assume_nonempty() }, root, &mut cmp)
+}
+
+/// Restores the max-heap rooted at the start of `r`,
+/// after the element at `node` may have become larger than its parent.
+///
+/// # Panics
+/// Panics if `node` is not contained within `r`.
+#[inline]
+pub fn sift_up<C, A, T, P>(c: &mut Container<C, A>, r: Range<C, P>, node: Index<C>)
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    T: Ord,
+{
+    sift_up_by(c, r, node, T::cmp)
+}
+
+/// Restores the max-heap rooted at the start of `r`, using the comparator function `cmp`,
+/// after the element at `node` may have become larger than its parent.
+///
+/// # Panics
+/// Panics if `node` is not contained within `r`.
+pub fn sift_up_by<C, A, T, P, F>(
+    c: &mut Container<C, A>,
+    r: Range<C, P>,
+    node: Index<C>,
+    mut cmp: F,
+) where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    assert!(
+        r.start() <= node.integer() && node.integer() < r.end(),
+        "sift_up: index {} is not within {}..{}",
+        node.integer(),
+        r.start(),
+        r.end()
+    );
+
+    let mut node = node;
+
+    while node.integer() > r.start() {
+        // The parent lies in between the start of `r` and `node`.
+        let parent = unsafe { Index::new(r.start() + (node.integer() - r.start() - 1) / 2) };
+
+        if cmp(&c[parent], &c[node]) != Ordering::Less {
+            return;
+        }
+
+        c.swap(parent, node);
+        node = parent;
+    }
+}
+
+/// A max-heap occupying a growing and shrinking prefix of a container, its extent.
+///
+/// Elements enter the heap by being swapped in from just past its extent,
+/// and leave it by being swapped out to just past it, like in heapsort.
+pub struct Heap<'c, C: for<'s> Contract<'s>, A, F> {
+    container: &'c mut Container<C, A>,
+    extent: Range<C>,
+    cmp: F,
+}
+
+impl<'c, C, A, T, F> Heap<'c, C, A, F>
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    /// Creates an empty heap at the start of `container`,
+    /// which is a max-heap with respect to the comparator function `cmp`.
+    #[inline]
+    pub fn new(container: &'c mut Container<C, A>, cmp: F) -> Self {
+        // The empty range at the start is within every container.
+        let extent = unsafe { Range::from_unknown(0, 0) };
+
+        Self {
+            container,
+            extent,
+            cmp,
+        }
+    }
+
+    /// Returns the range occupied by the heap.
+    #[inline]
+    pub fn extent(&self) -> Range<C> {
+        self.extent
+    }
+
+    /// Returns the number of elements in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.extent.len()
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.extent.is_empty()
+    }
+
+    /// Returns the container the heap lives in.
+    #[inline]
+    pub fn container(&self) -> &Container<C, A> {
+        self.container
+    }
+
+    /// Returns the index of the greatest element of the heap, or None if it is empty.
+    #[inline]
+    pub fn peek(&self) -> Option<Index<C>> {
+        self.extent.nonempty().map(|extent| extent.first())
+    }
+
+    /// Adds the element at `index` to the heap, by swapping it to just past the extent,
+    /// and growing the extent over it.
+    ///
+    /// # Panics
+    /// Panics if `index` lies within the extent.
+    pub fn push_from(&mut self, index: Index<C>) {
+        let end = self.extent.end();
+
+        assert!(
+            index.integer() >= end,
+            "push_from: index {} is within the heap 0..{}",
+            index.integer(),
+            end
+        );
+
+        // `end` is at most `index`, so it lies within the container.
+        let last = unsafe { Index::new(end) };
+
+        if last != index {
+            self.container.swap(last, index);
+        }
+
+        self.extent = unsafe { Range::from_unknown(0, end + 1) };
+        sift_up_by(self.container, self.extent, last, &mut self.cmp);
+    }
+
+    /// Removes the greatest element from the heap, by swapping it to the end of the extent,
+    /// and shrinking the extent to exclude it.
+    ///
+    /// Returns the index the element ended up at, or None if the heap is empty.
+    pub fn pop_to_end(&mut self) -> Option<Index<C>> {
+        let extent = self.extent.nonempty()?;
+        let (first, last) = (extent.first(), extent.last());
+
+        if first != last {
+            self.container.swap(first, last);
+        }
+
+        self.extent = extent.head();
+
+        if let Some(extent) = self.extent.nonempty() {
+            sift_down_by(self.container, extent, extent.first(), &mut self.cmp);
+        }
+
+        Some(last)
+    }
+
+    /// Replaces the greatest element of the heap with the element at `index`,
+    /// which lies outside of the extent, by swapping both.
+    ///
+    /// Returns the index the greatest element ended up at, or None if the heap is empty.
+    ///
+    /// # Panics
+    /// Panics if `index` lies within the extent.
+    pub fn replace_from(&mut self, index: Index<C>) -> Option<Index<C>> {
+        assert!(
+            index.integer() >= self.extent.end(),
+            "replace_from: index {} is within the heap 0..{}",
+            index.integer(),
+            self.extent.end()
+        );
+
+        let extent = self.extent.nonempty()?;
+
+        self.container.swap(extent.first(), index);
+        sift_down_by(self.container, extent, extent.first(), &mut self.cmp);
+
+        Some(index)
+    }
+}
+
+/// Moves the `k` greatest elements of `c` to its start, in descending order,
+/// and returns the range they occupy.
+///
+/// If `c` holds fewer than `k` elements, all of them are sorted in descending order.
+/// This takes `O(n log k)` time, and leaves the other elements in an unspecified order.
+#[inline]
+pub fn top_k<C, A, T>(c: &mut Container<C, A>, k: usize) -> Range<C>
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    T: Ord,
+{
+    top_k_by(c, k, T::cmp)
+}
+
+/// Moves the `k` greatest elements of `c` with respect to the comparator function `cmp`
+/// to its start, in descending order, and returns the range they occupy.
+pub fn top_k_by<C, A, T, F>(c: &mut Container<C, A>, k: usize, mut cmp: F) -> Range<C>
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let all = c.range();
+
+    // A min-heap of the greatest elements seen so far, the root being the least of them.
+    let mut heap = Heap::new(c, |a: &T, b: &T| cmp(b, a));
+
+    for index in all {
+        if heap.len() < k {
+            heap.push_from(index);
+        } else if let Some(least) = heap.peek() {
+            let Heap { container, cmp, .. } = &mut heap;
+
+            if cmp(&container[index], &container[least]) == Ordering::Less {
+                heap.replace_from(index);
+            }
+        }
+    }
+
+    let extent = heap.extent();
+
+    // Popping the least elements to the back leaves the extent sorted in descending order.
+    while heap.pop_to_end().is_some() {}
+
+    extent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{region, test_util::xorshift};
+
+    #[test]
+    fn heapsort_sorts() {
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+
+        for len in [0, 1, 2, 3, 10, 257] {
+            let mut v: Vec<u64> = (0..len).map(|_| xorshift(&mut state) % 50).collect();
+
+            let mut expected = v.clone();
+            expected.sort_unstable();
+
+            region(v.as_mut_slice(), |mut s| {
+                let r = s.range();
+                heapsort(&mut s, r);
+            });
+
+            assert_eq!(v, expected);
+        }
+    }
+
+    #[test]
+    fn heapsort_subrange() {
+        let mut v = vec![9, 8, 7, 6, 5, 4, 3];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range(1, 6).unwrap();
+            heapsort_by(&mut s, r, |a, b| b.cmp(a));
+        });
+
+        assert_eq!(v, [9, 8, 7, 6, 5, 4, 3]);
+    }
+
+    #[test]
+    fn heap_on_prefix() {
+        let mut v = vec![0u32; 8];
+
+        region(v.as_mut_slice(), |mut s| {
+            // Push 5 elements into a heap occupying a growing prefix of the container.
+            for (len, value) in [3, 9, 1, 7, 4].iter().enumerate() {
+                let heap = s.vet_range(0, len + 1).unwrap();
+                let node = s.vet(len).unwrap();
+
+                s[node] = *value;
+                sift_up(&mut s, heap, node);
+            }
+
+            // Pop them again, largest first.
+            let mut popped = Vec::new();
+            for len in (1..=5).rev() {
+                let (root, last) = (s.vet(0).unwrap(), s.vet(len - 1).unwrap());
+                popped.push(s[root]);
+                s.swap(root, last);
+
+                if let Ok(heap) = s.vet_range_nonempty(0, len - 1) {
+                    sift_down(&mut s, heap, heap.first());
+                }
+            }
+
+            assert_eq!(popped, [9, 7, 4, 3, 1]);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "sift_down: index 3 is not within 0..3")]
+    fn sift_down_outside() {
+        region(vec![1, 2, 3, 4], |mut s| {
+            let r = s.vet_range(0, 3).unwrap();
+            let root = s.vet(3).unwrap();
+            sift_down(&mut s, r, root);
+        });
+    }
+
+    #[test]
+    fn heap_push_and_pop() {
+        let mut v = vec![4, 8, 1, 9, 3];
+
+        region(v.as_mut_slice(), |mut s| {
+            let all = s.range();
+            let mut heap = Heap::new(&mut s, u32::cmp);
+            assert!(heap.peek().is_none());
+
+            for index in all {
+                heap.push_from(index);
+                assert!(
+                    heap.extent() == heap.container().vet_range(0, index.integer() + 1).unwrap()
+                );
+            }
+
+            let top = heap.peek().unwrap();
+            assert_eq!(heap.container()[top], 9);
+
+            let mut popped = Vec::new();
+            while let Some(index) = heap.pop_to_end() {
+                assert_eq!(index.integer(), heap.len());
+                popped.push(heap.container()[index]);
+            }
+            assert_eq!(popped, [9, 8, 4, 3, 1]);
+        });
+
+        assert_eq!(v, [1, 3, 4, 8, 9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "push_from: index 1 is within the heap 0..2")]
+    fn heap_push_from_inside() {
+        region(vec![1, 2, 3], |mut s| {
+            let (a, b) = (s.vet(0).unwrap(), s.vet(1).unwrap());
+            let mut heap = Heap::new(&mut s, u32::cmp);
+
+            heap.push_from(a);
+            heap.push_from(b);
+            heap.push_from(b);
+        });
+    }
+
+    #[test]
+    fn top_k_greatest() {
+        let mut v = vec![5, 1, 9, 3, 7, 9, 2, 8, 6, 4];
+
+        region(v.as_mut_slice(), |mut s| {
+            let top = top_k(&mut s, 3);
+            assert_eq!(s[top], [9, 9, 8]);
+
+            let none = top_k(&mut s, 0);
+            assert!(none.is_empty());
+
+            let all = top_k_by(&mut s, 20, |a, b| b.cmp(a));
+            assert_eq!(s[all], [1, 2, 3, 4, 5, 6, 7, 8, 9, 9]);
+        });
+    }
+}
@@ -0,0 +1,62 @@
+use core::cmp::Ordering;
+
+use crate::{
+    container::{traits::GetUncheckedMut, Container},
+    core::{range::Range, seal::Contract},
+};
+
+/// Sorts the elements within `r` with insertion sort.
+///
+/// This sort is stable, and performs well on small or nearly sorted ranges.
+#[inline]
+pub fn insertion_sort<C, A, T, P>(c: &mut Container<C, A>, r: Range<C, P>)
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    T: Ord,
+{
+    insertion_sort_by(c, r, T::cmp)
+}
+
+/// Sorts the elements within `r` with insertion sort, using the comparator function `cmp`.
+///
+/// This sort is stable, and performs well on small or nearly sorted ranges.
+#[inline]
+pub fn insertion_sort_by<C, A, T, P, F>(c: &mut Container<C, A>, r: Range<C, P>, mut cmp: F)
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    c.insertion_sort_range(r, &mut cmp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::region;
+
+    #[test]
+    fn insertion_sort_is_stable() {
+        let mut v = vec![(3, 'a'), (1, 'b'), (3, 'c'), (2, 'd'), (1, 'e')];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range();
+            insertion_sort_by(&mut s, r, |a, b| a.0.cmp(&b.0));
+        });
+
+        assert_eq!(v, [(1, 'b'), (1, 'e'), (2, 'd'), (3, 'a'), (3, 'c')]);
+    }
+
+    #[test]
+    fn insertion_sort_empty() {
+        let mut v: Vec<u32> = vec![];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range();
+            insertion_sort(&mut s, r);
+        });
+
+        assert!(v.is_empty());
+    }
+}
@@ -0,0 +1,167 @@
+use core::cmp::Ordering;
+
+use crate::{
+    container::{
+        error::LengthMismatch,
+        traits::{GetUnchecked, GetUncheckedMut},
+        Container,
+    },
+    core::{range::Range, seal::Contract},
+};
+
+/// Merges the sorted ranges `a` and `b` of `src` into the range `out` of `dst`.
+///
+/// This merge is stable: of equal elements, those of `a` come first.
+/// Returns an error if the length of `out` is not the sum of the lengths of `a` and `b`,
+/// in which case nothing is written.
+#[inline]
+pub fn merge<C, A, D, B, T, P, Q, R>(
+    src: &Container<C, A>,
+    a: Range<C, P>,
+    b: Range<C, Q>,
+    dst: &mut Container<D, B>,
+    out: Range<D, R>,
+) -> Result<(), LengthMismatch>
+where
+    C: for<'s> Contract<'s>,
+    D: for<'s> Contract<'s>,
+    A: GetUnchecked<Item = T>,
+    B: GetUncheckedMut<Item = T>,
+    T: Ord + Clone,
+{
+    merge_by(src, a, b, dst, out, T::cmp)
+}
+
+/// Merges the ranges `a` and `b` of `src`, sorted according to the comparator function `cmp`,
+/// into the range `out` of `dst`.
+///
+/// This merge is stable: of equal elements, those of `a` come first.
+/// Returns an error if the length of `out` is not the sum of the lengths of `a` and `b`,
+/// in which case nothing is written.
+pub fn merge_by<C, A, D, B, T, P, Q, R, F>(
+    src: &Container<C, A>,
+    a: Range<C, P>,
+    b: Range<C, Q>,
+    dst: &mut Container<D, B>,
+    out: Range<D, R>,
+    mut cmp: F,
+) -> Result<(), LengthMismatch>
+where
+    C: for<'s> Contract<'s>,
+    D: for<'s> Contract<'s>,
+    A: GetUnchecked<Item = T>,
+    B: GetUncheckedMut<Item = T>,
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if out.len() != a.len() + b.len() {
+        return Err(LengthMismatch::new(out.len(), a.len() + b.len()));
+    }
+
+    let (mut rest_a, mut rest_b) = (a.into_iter(), b.into_iter());
+    let (mut next_a, mut next_b) = (rest_a.next(), rest_b.next());
+
+    // `out` is exactly as long as both ranges together,
+    // so one of the cursors always has an element left.
+    for o in out {
+        let next = match (next_a, next_b) {
+            (Some(i), Some(j)) if cmp(&src[j], &src[i]) == Ordering::Less => {
+                next_b = rest_b.next();
+                j
+            }
+            (Some(i), _) => {
+                next_a = rest_a.next();
+                i
+            }
+            (None, Some(j)) => {
+                next_b = rest_b.next();
+                j
+            }
+            (None, None) => break,
+        };
+
+        dst[o].clone_from(&src[next]);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::region;
+
+    fn merged(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let src: Vec<u32> = a.iter().chain(b).copied().collect();
+        let mut dst = vec![0; src.len()];
+
+        region(src.as_slice(), |src| {
+            region(dst.as_mut_slice(), |mut dst| {
+                let a = src.vet_range(0, a.len()).unwrap();
+                let b = src.vet_range(a.len(), src.len()).unwrap();
+                let out = dst.range();
+
+                merge(&src, a, b, &mut dst, out).unwrap();
+            })
+        });
+
+        let mut expected = src;
+        expected.sort();
+        assert_eq!(dst, expected);
+
+        dst
+    }
+
+    #[test]
+    fn merge_interleaved() {
+        assert_eq!(merged(&[1, 3, 5, 7], &[2, 4, 6]), [1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(merged(&[1, 2, 9], &[3, 4, 5, 6]), [1, 2, 3, 4, 5, 6, 9]);
+    }
+
+    #[test]
+    fn merge_one_empty() {
+        assert_eq!(merged(&[], &[1, 2, 3]), [1, 2, 3]);
+        assert_eq!(merged(&[1, 2, 3], &[]), [1, 2, 3]);
+        assert!(merged(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn merge_is_stable() {
+        let src = vec![(1, 'a'), (2, 'a'), (2, 'b'), (1, 'c'), (2, 'c'), (3, 'c')];
+        let mut dst = vec![(0, ' '); 6];
+
+        region(src.as_slice(), |src| {
+            region(dst.as_mut_slice(), |mut dst| {
+                let (a, b) = (src.vet_range(0, 3).unwrap(), src.vet_range(3, 6).unwrap());
+                let out = dst.range();
+
+                merge_by(&src, a, b, &mut dst, out, |x, y| x.0.cmp(&y.0)).unwrap();
+            })
+        });
+
+        assert_eq!(
+            dst,
+            [(1, 'a'), (1, 'c'), (2, 'a'), (2, 'b'), (2, 'c'), (3, 'c')]
+        );
+    }
+
+    #[test]
+    fn merge_length_mismatch() {
+        let src = vec![1, 2, 3];
+        let mut dst = vec![0; 4];
+
+        region(src.as_slice(), |src| {
+            region(dst.as_mut_slice(), |mut dst| {
+                let (a, b) = (src.vet_range(0, 1).unwrap(), src.vet_range(1, 3).unwrap());
+                let out = dst.range();
+
+                assert_eq!(
+                    merge(&src, a, b, &mut dst, out),
+                    Err(LengthMismatch::new(4, 3))
+                );
+            })
+        });
+
+        assert_eq!(dst, [0; 4]);
+    }
+}
@@ -0,0 +1,230 @@
+use core::cmp::Ordering;
+
+use crate::{
+    container::{traits::GetUncheckedMut, Container},
+    core::{proof::NonEmpty, range::Range, seal::Contract},
+};
+
+/// Merges the adjacent sorted ranges `left` and `right` in place,
+/// such that the range spanning both is sorted afterwards.
+///
+/// This merge is stable and does not allocate: instead of copying into scratch space,
+/// it splits both ranges and rotates the middle parts past each other,
+/// which takes `O(n log n)` time where a merge with scratch space takes `O(n)`.
+///
+/// # Panics
+/// Panics if `right` does not start where `left` ends.
+#[inline]
+pub fn merge_in_place<C, A, T>(
+    c: &mut Container<C, A>,
+    left: Range<C, NonEmpty>,
+    right: Range<C, NonEmpty>,
+) where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    T: Ord,
+{
+    merge_in_place_by(c, left, right, T::cmp)
+}
+
+/// Merges the adjacent ranges `left` and `right`, sorted according to the comparator function `cmp`,
+/// in place, such that the range spanning both is sorted afterwards.
+///
+/// # Panics
+/// Panics if `right` does not start where `left` ends.
+pub fn merge_in_place_by<C, A, T, F>(
+    c: &mut Container<C, A>,
+    left: Range<C, NonEmpty>,
+    right: Range<C, NonEmpty>,
+    mut cmp: F,
+) where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    assert!(
+        left.end() == right.start(),
+        "merge_in_place: ranges {}..{} and {}..{} are not adjacent",
+        left.start(),
+        left.end(),
+        right.start(),
+        right.end()
+    );
+
+    merge_adjacent(c, left.forget_proof(), right.forget_proof(), &mut cmp)
+}
+
+/// Merges the sorted ranges `left` and `right`, where `right` starts where `left` ends.
+fn merge_adjacent<C, A, T, F>(
+    c: &mut Container<C, A>,
+    mut left: Range<C>,
+    mut right: Range<C>,
+    cmp: &mut F,
+) where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    loop {
+        let (lhs, rhs) = match (left.nonempty(), right.nonempty()) {
+            (Some(lhs), Some(rhs)) => (lhs, rhs),
+            _ => return,
+        };
+
+        if lhs.len() + rhs.len() == 2 {
+            if cmp(&c[rhs.first()], &c[lhs.first()]) == Ordering::Less {
+                c.swap(lhs.first(), rhs.first());
+            }
+            return;
+        }
+
+        // Split the longer range in half, and the other one where the middle element
+        // of the longer one would be inserted. Equal elements of `left` stay in front.
+        let (cut_left, cut_right) = if lhs.len() >= rhs.len() {
+            let mid = lhs.upper_middle();
+            let pivot = &c[mid];
+            let cut = c.partition_point(rhs, |x| cmp(x, pivot) == Ordering::Less);
+
+            (mid.integer(), cut.integer())
+        } else {
+            let mid = rhs.upper_middle();
+            let pivot = &c[mid];
+            let cut = c.partition_point(lhs, |x| cmp(pivot, x) != Ordering::Less);
+
+            (cut.integer(), mid.integer())
+        };
+
+        let middle = lhs.end();
+        let new_middle = cut_left + (cut_right - middle);
+
+        // `lhs.start() <= cut_left <= middle <= cut_right <= rhs.end()`,
+        // so every range below lies within `lhs.start()..rhs.end()`.
+        let (first, second) = unsafe {
+            let rotated = Range::<C>::from_unknown(cut_left, cut_right);
+
+            if let Some(rotated) = rotated.nonempty() {
+                c.rotate_range_left(rotated, middle - cut_left);
+            }
+
+            (
+                (
+                    Range::<C>::from_unknown(lhs.start(), cut_left),
+                    Range::<C>::from_unknown(cut_left, new_middle),
+                ),
+                (
+                    Range::<C>::from_unknown(new_middle, cut_right),
+                    Range::<C>::from_unknown(cut_right, rhs.end()),
+                ),
+            )
+        };
+
+        // Recursing into the smaller half bounds the depth of the recursion to log(n).
+        if first.0.len() + first.1.len() < second.0.len() + second.1.len() {
+            merge_adjacent(c, first.0, first.1, cmp);
+            left = second.0;
+            right = second.1;
+        } else {
+            merge_adjacent(c, second.0, second.1, cmp);
+            left = first.0;
+            right = first.1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::region;
+
+    fn merged(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut v: Vec<u32> = a.iter().chain(b).copied().collect();
+
+        region(v.as_mut_slice(), |mut s| {
+            let left = s.vet_range_nonempty(0, a.len()).unwrap();
+            let right = s.vet_range_nonempty(a.len(), a.len() + b.len()).unwrap();
+
+            merge_in_place(&mut s, left, right);
+        });
+
+        let mut expected: Vec<u32> = a.iter().chain(b).copied().collect();
+        expected.sort();
+        assert_eq!(v, expected);
+
+        v
+    }
+
+    #[test]
+    fn merge_in_place_interleaved() {
+        let evens: Vec<u32> = (0..200).map(|x| 2 * x).collect();
+        let odds: Vec<u32> = (0..200).map(|x| 2 * x + 1).collect();
+
+        merged(&evens, &odds);
+        merged(&odds, &evens);
+        merged(&[1, 3, 5, 7], &[2, 4, 6]);
+
+        // Alternating runs of growing length.
+        let (mut a, mut b) = (Vec::new(), Vec::new());
+        for run in 0..20 {
+            let base = run * run * 2;
+            a.extend(base..base + run);
+            b.extend(base + run..base + 2 * run);
+        }
+        merged(&a, &b);
+    }
+
+    #[test]
+    fn merge_in_place_skewed() {
+        let many: Vec<u32> = (0..1000).collect();
+
+        assert_eq!(merged(&[500], &many)[499..502], [499, 500, 500]);
+        assert_eq!(merged(&many, &[500])[499..502], [499, 500, 500]);
+        assert_eq!(merged(&[2000], &many)[1000], 2000);
+        assert_eq!(merged(&many, &[0])[..2], [0, 0]);
+        merged(&many[..3], &many);
+        merged(&many, &many[997..]);
+    }
+
+    #[test]
+    fn merge_in_place_is_stable() {
+        let mut v = vec![
+            (1, 'a'),
+            (2, 'a'),
+            (2, 'b'),
+            (3, 'a'),
+            (1, 'c'),
+            (2, 'c'),
+            (3, 'c'),
+        ];
+
+        region(v.as_mut_slice(), |mut s| {
+            let left = s.vet_range_nonempty(0, 4).unwrap();
+            let right = s.vet_range_nonempty(4, 7).unwrap();
+
+            merge_in_place_by(&mut s, left, right, |x, y| x.0.cmp(&y.0));
+        });
+
+        assert_eq!(
+            v,
+            [
+                (1, 'a'),
+                (1, 'c'),
+                (2, 'a'),
+                (2, 'b'),
+                (2, 'c'),
+                (3, 'a'),
+                (3, 'c')
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "merge_in_place: ranges 0..2 and 3..5 are not adjacent")]
+    fn merge_in_place_not_adjacent() {
+        region(vec![1, 2, 0, 3, 4], |mut s| {
+            let left = s.vet_range_nonempty(0, 2).unwrap();
+            let right = s.vet_range_nonempty(3, 5).unwrap();
+
+            merge_in_place(&mut s, left, right);
+        });
+    }
+}
@@ -0,0 +1,25 @@
+//! Algorithms built entirely on top of branded containers, ranges and indices.
+//!
+//! This module is only available with the `algorithms` feature enabled.
+pub mod find;
+pub use find::*;
+pub mod gallop;
+pub use gallop::*;
+pub mod heap;
+pub use heap::*;
+pub mod insertion;
+pub use insertion::*;
+pub mod merge;
+pub use merge::*;
+pub mod merge_in_place;
+pub use merge_in_place::*;
+pub mod network;
+pub use network::*;
+pub mod partition;
+pub use partition::*;
+pub mod permutation;
+pub use permutation::*;
+pub mod quicksort;
+pub use quicksort::*;
+pub mod shuffle;
+pub use shuffle::*;
@@ -0,0 +1,234 @@
+use core::cmp::Ordering;
+
+use super::insertion::insertion_sort_by;
+use crate::{
+    container::{traits::GetUncheckedMut, Container},
+    core::{index::Index, proof::ExactLen, range::Range, seal::Contract},
+};
+
+/// A sorting network for ranges of a fixed length.
+///
+/// This trait is implemented for [`ExactLen<N>`] with `N` in `2..=8`,
+/// using the networks with the fewest known comparators.
+pub trait SortingNetwork: Sized {
+    /// Sorts the elements within `r` using the comparator function `cmp`.
+    fn sort_by<C, A, T, F>(c: &mut Container<C, A>, r: Range<C, Self>, cmp: &mut F)
+    where
+        C: for<'s> Contract<'s>,
+        A: GetUncheckedMut<Item = T>,
+        F: FnMut(&T, &T) -> Ordering;
+}
+
+/// Swaps the elements at offsets `a` and `b` from the start of `r`, if they are out of order.
+///
+/// # Unsafe
+/// This function is marked unsafe,
+/// because it can not be proved both offsets lie within `r`.
+#[inline(always)]
+unsafe fn compare_swap<C, A, T, F, P>(
+    c: &mut Container<C, A>,
+    r: Range<C, P>,
+    a: usize,
+    b: usize,
+    cmp: &mut F,
+) where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    c.compare_swap(Index::new(r.start() + a), Index::new(r.start() + b), cmp)
+}
+
+macro_rules! sorting_networks {
+    ($($n:literal => [$(($a:literal, $b:literal)),* $(,)?];)*) => {$(
+        impl SortingNetwork for ExactLen<$n> {
+            #[inline]
+            fn sort_by<C, A, T, F>(c: &mut Container<C, A>, r: Range<C, Self>, cmp: &mut F)
+            where
+                C: for<'s> Contract<'s>,
+                A: GetUncheckedMut<Item = T>,
+                F: FnMut(&T, &T) -> Ordering,
+            {
+                // The length of `r` is proven to be exactly $n,
+                // and every comparator lies within `0..$n`.
+                unsafe {
+                    $(compare_swap(c, r, $a, $b, cmp);)*
+                }
+            }
+        }
+    )*};
+}
+
+sorting_networks! {
+    2 => [(0, 1)];
+    3 => [(0, 2), (0, 1), (1, 2)];
+    4 => [(0, 2), (1, 3), (0, 1), (2, 3), (1, 2)];
+    5 => [
+        (0, 3), (1, 4), (0, 2), (1, 3), (0, 1), (2, 4), (1, 2), (3, 4), (2, 3),
+    ];
+    6 => [
+        (0, 5), (1, 3), (2, 4), (1, 2), (3, 4), (0, 3), (2, 5), (0, 1), (2, 3), (4, 5),
+        (1, 2), (3, 4),
+    ];
+    7 => [
+        (0, 6), (2, 3), (4, 5), (0, 2), (1, 4), (3, 6), (0, 1), (2, 5), (3, 4), (1, 2),
+        (4, 6), (2, 3), (4, 5), (1, 2), (3, 4), (5, 6),
+    ];
+}
+
+impl SortingNetwork for ExactLen<8> {
+    #[inline]
+    fn sort_by<C, A, T, F>(c: &mut Container<C, A>, r: Range<C, Self>, cmp: &mut F)
+    where
+        C: for<'s> Contract<'s>,
+        A: GetUncheckedMut<Item = T>,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        c.sort_network_8(r, cmp)
+    }
+}
+
+/// Sorts the elements within `r` with a sorting network for length `N`.
+#[inline]
+pub fn sort_network<C, A, T, const N: usize>(c: &mut Container<C, A>, r: Range<C, ExactLen<N>>)
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    T: Ord,
+    ExactLen<N>: SortingNetwork,
+{
+    sort_network_by(c, r, T::cmp)
+}
+
+/// Sorts the elements within `r` with a sorting network for length `N`,
+/// using the comparator function `cmp`.
+#[inline]
+pub fn sort_network_by<C, A, T, F, const N: usize>(
+    c: &mut Container<C, A>,
+    r: Range<C, ExactLen<N>>,
+    mut cmp: F,
+) where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+    ExactLen<N>: SortingNetwork,
+{
+    ExactLen::<N>::sort_by(c, r, &mut cmp)
+}
+
+/// Sorts the elements within `r`,
+/// using a sorting network if its length is within `2..=8`, and insertion sort otherwise.
+#[inline]
+pub fn sort_small<C, A, T, P>(c: &mut Container<C, A>, r: Range<C, P>)
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    T: Ord,
+{
+    sort_small_by(c, r, T::cmp)
+}
+
+/// Sorts the elements within `r` using the comparator function `cmp`,
+/// using a sorting network if its length is within `2..=8`, and insertion sort otherwise.
+#[inline]
+pub fn sort_small_by<C, A, T, P, F>(c: &mut Container<C, A>, r: Range<C, P>, mut cmp: F)
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    macro_rules! dispatch {
+        ($($n:literal),*) => {$(
+            if let Some(exact) = r.with_exact_size::<$n>() {
+                return ExactLen::<$n>::sort_by(c, exact, &mut cmp);
+            }
+        )*};
+    }
+
+    dispatch!(2, 3, 4, 5, 6, 7, 8);
+
+    insertion_sort_by(c, r, cmp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        region,
+        test_util::{permutations, xorshift},
+    };
+
+    fn network<const N: usize>(mut v: Vec<u32>) -> Vec<u32>
+    where
+        ExactLen<N>: SortingNetwork,
+    {
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range().with_exact_size::<N>().unwrap();
+            sort_network(&mut s, r);
+        });
+
+        v
+    }
+
+    #[test]
+    fn networks_sort_all_permutations() {
+        for perm in permutations(&[0, 1]) {
+            assert_eq!(network::<2>(perm), [0, 1]);
+        }
+
+        for perm in permutations(&[0, 1, 2]) {
+            assert_eq!(network::<3>(perm), [0, 1, 2]);
+        }
+
+        for perm in permutations(&[0, 1, 2, 3]) {
+            assert_eq!(network::<4>(perm), [0, 1, 2, 3]);
+        }
+
+        for perm in permutations(&[0, 1, 2, 3, 4]) {
+            assert_eq!(network::<5>(perm), [0, 1, 2, 3, 4]);
+        }
+    }
+
+    #[test]
+    fn networks_sort_all_binary_inputs() {
+        // By the 0-1 principle, a network that sorts every sequence of zeroes and ones sorts
+        // every sequence.
+        fn check<const N: usize>()
+        where
+            ExactLen<N>: SortingNetwork,
+        {
+            for bits in 0..1u32 << N {
+                let v: Vec<u32> = (0..N).map(|i| (bits >> i) & 1).collect();
+                let mut expected = v.clone();
+                expected.sort_unstable();
+
+                assert_eq!(network::<N>(v), expected);
+            }
+        }
+
+        check::<6>();
+        check::<7>();
+        check::<8>();
+    }
+
+    #[test]
+    fn sort_small_agrees_with_sort_unstable() {
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+
+        for len in 0..20 {
+            for _ in 0..10 {
+                let mut v: Vec<u64> = (0..len).map(|_| xorshift(&mut seed) % 10).collect();
+
+                let mut expected = v.clone();
+                expected.sort_unstable();
+
+                region(v.as_mut_slice(), |mut s| {
+                    let r = s.range();
+                    sort_small(&mut s, r);
+                });
+
+                assert_eq!(v, expected);
+            }
+        }
+    }
+}
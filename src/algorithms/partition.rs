@@ -0,0 +1,120 @@
+use core::cmp::Ordering;
+
+use crate::{
+    container::{traits::GetUncheckedMut, Container},
+    core::{range::Range, seal::Contract},
+};
+
+/// Rearranges the elements within `r` into three consecutive ranges, of the elements
+/// for which `f` returns [`Ordering::Less`], [`Ordering::Equal`] and [`Ordering::Greater`] respectively,
+/// and returns those ranges.
+///
+/// This partition is unstable, and calls `f` exactly once for every element within `r`.
+/// It is the partition of choice for ranges with many equal elements.
+#[inline]
+pub fn partition3<C, A, T, P, F>(
+    c: &mut Container<C, A>,
+    r: Range<C, P>,
+    f: F,
+) -> (Range<C>, Range<C>, Range<C>)
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    F: FnMut(&T) -> Ordering,
+{
+    c.partition3(r, f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::region;
+
+    fn partitioned(v: &mut [u32], pivot: u32) -> [(usize, usize); 3] {
+        region(v, |mut s| {
+            let r = s.range();
+            let (lt, eq, gt) = partition3(&mut s, r, |x| x.cmp(&pivot));
+
+            assert!(s[lt].iter().all(|&x| x < pivot));
+            assert!(s[eq].iter().all(|&x| x == pivot));
+            assert!(s[gt].iter().all(|&x| x > pivot));
+
+            [lt, eq, gt].map(|r| (r.start(), r.end()))
+        })
+    }
+
+    #[test]
+    fn partition3_mixed() {
+        let mut v = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        let mut sorted = v.clone();
+        sorted.sort_unstable();
+
+        assert_eq!(partitioned(&mut v, 5), [(0, 6), (6, 9), (9, 11)]);
+
+        v.sort_unstable();
+        assert_eq!(v, sorted);
+    }
+
+    #[test]
+    fn partition3_all_equal() {
+        let mut v = vec![7; 10];
+        assert_eq!(partitioned(&mut v, 7), [(0, 0), (0, 10), (10, 10)]);
+        assert_eq!(partitioned(&mut v, 0), [(0, 0), (0, 0), (0, 10)]);
+        assert_eq!(partitioned(&mut v, 9), [(0, 10), (10, 10), (10, 10)]);
+        assert_eq!(partitioned(&mut [], 9), [(0, 0), (0, 0), (0, 0)]);
+    }
+
+    #[test]
+    fn partition3_already_partitioned() {
+        let mut v = vec![1, 2, 2, 5, 5, 5, 8, 9];
+        assert_eq!(partitioned(&mut v, 5), [(0, 3), (3, 6), (6, 8)]);
+        assert_eq!(v, [1, 2, 2, 5, 5, 5, 9, 8]);
+    }
+
+    #[test]
+    fn partition3_subrange() {
+        let mut v = vec![9, 3, 1, 2, 3, 0];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range(1, 5).unwrap();
+            let (lt, eq, gt) = partition3(&mut s, r, |x| x.cmp(&2));
+
+            assert_eq!(s[lt], [1]);
+            assert_eq!(s[eq], [2]);
+            assert_eq!(gt.untrusted(), 3..5);
+        });
+
+        assert_eq!(v[0], 9);
+        assert_eq!(v[5], 0);
+    }
+
+    #[test]
+    fn partition3_drop() {
+        use std::cell::Cell;
+
+        struct Tracked<'a>(u32, &'a Cell<usize>);
+
+        impl Drop for Tracked<'_> {
+            fn drop(&mut self) {
+                self.1.set(self.1.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        let mut v: Vec<_> = (0..50).map(|n| Tracked(n * 7 % 50, &drops)).collect();
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range();
+            partition3(&mut s, r, |t| (t.0 / 10).cmp(&2));
+        });
+
+        assert_eq!(drops.get(), 0);
+
+        let mut keys: Vec<u32> = v.iter().map(|t| t.0).collect();
+        keys.sort_unstable();
+        assert_eq!(keys, (0..50).collect::<Vec<_>>());
+
+        drop(v);
+        assert_eq!(drops.get(), 50);
+    }
+}
@@ -0,0 +1,146 @@
+use core::cmp::Ordering;
+
+use crate::{
+    container::{traits::GetUncheckedMut, Container},
+    core::{range::Range, seal::Contract},
+};
+
+/// Rearranges the elements within `r` into the lexicographically next permutation.
+///
+/// Returns `true` if such a permutation exists. Otherwise,
+/// returns `false` and rearranges `r` into the first permutation (sorted ascending).
+#[inline]
+pub fn next_permutation<C, A, T, P>(c: &mut Container<C, A>, r: Range<C, P>) -> bool
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    T: Ord,
+{
+    c.next_permutation(r)
+}
+
+/// Rearranges the elements within `r` into the next permutation
+/// with respect to the comparator function `cmp`.
+///
+/// Returns `true` if such a permutation exists. Otherwise,
+/// returns `false` and rearranges `r` into the first permutation (sorted ascending).
+#[inline]
+pub fn next_permutation_by<C, A, T, P, F>(
+    c: &mut Container<C, A>,
+    r: Range<C, P>,
+    mut cmp: F,
+) -> bool
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    c.permute(r, |a, b| cmp(a, b) == Ordering::Less)
+}
+
+/// Rearranges the elements within `r` into the lexicographically previous permutation.
+///
+/// Returns `true` if such a permutation exists. Otherwise,
+/// returns `false` and rearranges `r` into the last permutation (sorted descending).
+#[inline]
+pub fn prev_permutation<C, A, T, P>(c: &mut Container<C, A>, r: Range<C, P>) -> bool
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    T: Ord,
+{
+    c.prev_permutation(r)
+}
+
+/// Rearranges the elements within `r` into the previous permutation
+/// with respect to the comparator function `cmp`.
+///
+/// Returns `true` if such a permutation exists. Otherwise,
+/// returns `false` and rearranges `r` into the last permutation (sorted descending).
+#[inline]
+pub fn prev_permutation_by<C, A, T, P, F>(
+    c: &mut Container<C, A>,
+    r: Range<C, P>,
+    mut cmp: F,
+) -> bool
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    c.permute(r, |a, b| cmp(a, b) == Ordering::Greater)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{region, test_util::permutations};
+
+    #[test]
+    fn next_permutation_visits_all() {
+        let mut v = vec![9, 1, 2, 3, 4, 5, 6, 9];
+        let mut seen = Vec::new();
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range(1, 7).unwrap();
+
+            loop {
+                seen.push(s[r].to_vec());
+
+                if !next_permutation(&mut s, r) {
+                    break;
+                }
+            }
+
+            // Wrapping around produces the first permutation again.
+            assert_eq!(s[r], [1, 2, 3, 4, 5, 6]);
+        });
+
+        assert_eq!(seen.len(), 720);
+        assert_eq!(seen, permutations(&[1, 2, 3, 4, 5, 6]));
+        assert_eq!((v[0], v[7]), (9, 9));
+    }
+
+    #[test]
+    fn prev_permutation_visits_all() {
+        let mut v = vec![6, 5, 4, 3, 2, 1];
+        let mut seen = Vec::new();
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range();
+
+            loop {
+                seen.push(s[r].to_vec());
+
+                if !prev_permutation(&mut s, r) {
+                    break;
+                }
+            }
+
+            assert_eq!(s[r], [6, 5, 4, 3, 2, 1]);
+        });
+
+        let mut expected = permutations(&[1, 2, 3, 4, 5, 6]);
+        expected.reverse();
+        assert_eq!(seen, expected);
+    }
+
+    #[test]
+    fn permutation_by_with_duplicates() {
+        let mut v = vec![(1, 'a'), (1, 'b'), (2, 'c')];
+        let mut count = 0;
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range();
+
+            // Elements comparing equal are not permuted among each other.
+            while next_permutation_by(&mut s, r, |a, b| a.0.cmp(&b.0)) {
+                count += 1;
+            }
+            assert!(!prev_permutation_by(&mut s, r, |a, b| a.0.cmp(&b.0)));
+        });
+
+        assert_eq!(count, 2);
+        assert_eq!(v, [(2, 'c'), (1, 'b'), (1, 'a')]);
+    }
+}
@@ -0,0 +1,82 @@
+use core::cmp::Ordering;
+
+use crate::{
+    container::{traits::GetUncheckedMut, Container},
+    core::{range::Range, seal::Contract},
+};
+
+/// Sorts the elements within `r` with quicksort.
+///
+/// This sort is unstable, does not allocate, and runs in `O(n log n)` in the worst case.
+#[inline]
+pub fn quicksort<C, A, T, P>(c: &mut Container<C, A>, r: Range<C, P>)
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    T: Ord,
+{
+    quicksort_by(c, r, T::cmp)
+}
+
+/// Sorts the elements within `r` with quicksort, using the comparator function `cmp`.
+///
+/// This sort is unstable, does not allocate, and runs in `O(n log n)` in the worst case:
+/// it forwards to [`Container::sort_range_unstable_by`], an introsort that falls back
+/// to heapsort when partitioning keeps turning out unbalanced.
+#[inline]
+pub fn quicksort_by<C, A, T, P, F>(c: &mut Container<C, A>, r: Range<C, P>, cmp: F)
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    c.sort_range_unstable_by(r, cmp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{region, test_util::xorshift};
+
+    fn check(mut v: Vec<u64>) {
+        let mut expected = v.clone();
+        expected.sort_unstable();
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range();
+            quicksort(&mut s, r);
+        });
+
+        assert_eq!(v, expected);
+    }
+
+    #[test]
+    fn quicksort_random() {
+        let mut state = 0x2545_f491_4f6c_dd1d;
+
+        for len in [0, 1, 2, 3, 17, 100, 1000, 4096] {
+            check((0..len).map(|_| xorshift(&mut state)).collect());
+            check((0..len).map(|_| xorshift(&mut state) % 4).collect());
+        }
+    }
+
+    #[test]
+    fn quicksort_patterns() {
+        check(vec![7; 1000]);
+        check((0..1000).collect());
+        check((0..1000).rev().collect());
+        check((0..1000).map(|x| x % 2).collect());
+    }
+
+    #[test]
+    fn quicksort_subrange() {
+        let mut v = vec![9, 8, 7, 6, 5, 4, 3, 2, 1];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range(2, 7).unwrap();
+            quicksort_by(&mut s, r, |a, b| a.cmp(b));
+        });
+
+        assert_eq!(v, [9, 8, 3, 4, 5, 6, 7, 2, 1]);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{
+    container::{traits::GetUncheckedMut, Container},
+    core::{range::Range, seal::Contract},
+};
+
+/// A source of uniformly distributed random numbers.
+///
+/// This is the subset of `rand::RngCore` the algorithms in this module need.
+/// With the `rand` feature enabled, it is implemented for every `rand::RngCore`;
+/// other random number generators can be plugged in with a one-line impl.
+pub trait Rng {
+    /// Returns the next random `u64`, all bits of which are uniformly distributed.
+    fn next_u64(&mut self) -> u64;
+}
+
+#[cfg(feature = "rand")]
+impl<R: rand::RngCore + ?Sized> Rng for R {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        rand::RngCore::next_u64(self)
+    }
+}
+
+/// Shuffles the elements within `r` with the Fisher-Yates shuffle,
+/// such that every permutation is equally likely, given a uniform `rng`.
+#[inline]
+pub fn shuffle<C, A, T, P, R>(c: &mut Container<C, A>, r: Range<C, P>, rng: &mut R)
+where
+    C: for<'s> Contract<'s>,
+    A: GetUncheckedMut<Item = T>,
+    R: Rng + ?Sized,
+{
+    let mut rest = r.forget_proof();
+
+    // Move a random element of the unshuffled range to its end, and shrink it.
+    while let Some(nonempty) = rest.nonempty() {
+        let chosen = nonempty.random_index(rng);
+        let last = nonempty.last();
+
+        if chosen != last {
+            c.swap(chosen, last);
+        }
+
+        rest = nonempty.head();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{region, test_util::xorshift};
+
+    struct XorShift(u64);
+
+    impl Rng for XorShift {
+        fn next_u64(&mut self) -> u64 {
+            xorshift(&mut self.0)
+        }
+    }
+
+    #[test]
+    fn shuffle_permutes() {
+        let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+        let mut v: Vec<u32> = (0..100).collect();
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range(10, 90).unwrap();
+            shuffle(&mut s, r, &mut rng);
+        });
+
+        assert_eq!(v[..10], (0..10).collect::<Vec<_>>()[..]);
+        assert_eq!(v[90..], (90..100).collect::<Vec<_>>()[..]);
+        assert_ne!(v[10..90], (10..90).collect::<Vec<_>>()[..]);
+
+        v.sort_unstable();
+        assert_eq!(v, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn random_index_within_range() {
+        let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+
+        region(vec![0u8; 10], |s| {
+            let r = s.vet_range_nonempty(3, 6).unwrap();
+            let mut hits = [0; 10];
+
+            for _ in 0..300 {
+                hits[r.random_index(&mut rng).integer()] += 1;
+            }
+
+            assert!(hits[..3].iter().chain(&hits[6..]).all(|&n| n == 0));
+            assert!(hits[3..6].iter().all(|&n| n > 50));
+        });
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn shuffle_with_rand() {
+        use rand::{rngs::mock::StepRng, RngCore};
+
+        let mut rng = StepRng::new(0x2545_f491_4f6c_dd1d, 0x9e37_79b9_7f4a_7c15);
+        let dyn_rng: &mut dyn RngCore = &mut rng;
+        let mut v: Vec<u32> = (0..20).collect();
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range();
+            shuffle(&mut s, r, dyn_rng);
+        });
+
+        assert_ne!(v, (0..20).collect::<Vec<_>>());
+
+        v.sort_unstable();
+        assert_eq!(v, (0..20).collect::<Vec<_>>());
+    }
+
+    /// Shuffles 4 elements many times, and checks whether the 24 permutations
+    /// occur about equally often with a chi-squared test.
+    #[test]
+    #[ignore]
+    fn shuffle_is_uniform() {
+        const ROUNDS: usize = 240_000;
+
+        let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+        let mut counts = [0usize; 24];
+
+        for _ in 0..ROUNDS {
+            let mut v = [0u8, 1, 2, 3];
+
+            region(&mut v[..], |mut s| {
+                let r = s.range();
+                shuffle(&mut s, r, &mut rng);
+            });
+
+            // Rank the permutation in the factorial number system.
+            let rank = (0..4).fold(0, |rank, i| {
+                let smaller_after = v[i + 1..].iter().filter(|&&x| x < v[i]).count();
+                rank * (4 - i) + smaller_after
+            });
+            counts[rank] += 1;
+        }
+
+        let expected = ROUNDS as f64 / 24.0;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|&n| (n as f64 - expected).powi(2) / expected)
+            .sum();
+
+        // The 99.9th percentile of the chi-squared distribution with 23 degrees of freedom.
+        assert!(chi_squared < 49.73, "chi-squared is {}", chi_squared);
+    }
+}
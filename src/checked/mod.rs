@@ -0,0 +1,5 @@
+//! Runtime validation of the container traits.
+//!
+//! This module is only available with the `paranoid` feature enabled.
+pub mod shadow;
+pub use shadow::*;
@@ -0,0 +1,362 @@
+use crate::container::traits::{
+    ContainerTrait, Contiguous, ContiguousMut, GetUnchecked, GetUncheckedMut, SplitUnchecked,
+    SplitUncheckedMut,
+};
+
+/// An adapter over any container `A`, which forwards every container trait to `A`,
+/// while asserting the invariants the traits promise at runtime.
+///
+/// Wrapping a custom container in a `Shadow` before passing it to [`crate::region`]
+/// runs the real algorithm with full validation of the container's unsafe trait impls,
+/// panicking with a descriptive message on the first violation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Shadow<A> {
+    container: A,
+}
+
+impl<A> Shadow<A> {
+    /// Wraps `container` in a validating shadow.
+    #[inline(always)]
+    pub fn new(container: A) -> Self {
+        Self { container }
+    }
+
+    /// Returns the wrapped container.
+    #[inline(always)]
+    pub fn into_inner(self) -> A {
+        self.container
+    }
+}
+
+impl<A: Contiguous> Shadow<A> {
+    /// Asserts that `begin`, `end` and `as_slice` of the wrapped container agree with each other,
+    /// and with `base_len`.
+    fn check_contiguous(&self) {
+        let slice = self.container.as_slice();
+        let len = self.container.base_len();
+
+        assert_eq!(
+            slice.len(),
+            len,
+            "Contiguous::as_slice returned a slice of length {}, but ContainerTrait::base_len is {}",
+            slice.len(),
+            len
+        );
+
+        assert_eq!(
+            self.container.begin(),
+            slice.as_ptr(),
+            "Contiguous::begin does not point to the start of Contiguous::as_slice"
+        );
+
+        assert_eq!(
+            self.container.end(),
+            slice.as_ptr().wrapping_add(len),
+            "Contiguous::end does not point {} elements past Contiguous::begin",
+            len
+        );
+    }
+}
+
+unsafe impl<A: ContainerTrait> ContainerTrait for Shadow<A> {
+    type Item = A::Item;
+
+    #[inline(always)]
+    fn base_len(&self) -> usize {
+        self.container.base_len()
+    }
+}
+
+unsafe impl<A: Contiguous> Contiguous for Shadow<A> {
+    fn begin(&self) -> *const Self::Item {
+        self.check_contiguous();
+        self.container.begin()
+    }
+
+    fn end(&self) -> *const Self::Item {
+        self.check_contiguous();
+        self.container.end()
+    }
+
+    fn as_slice(&self) -> &[Self::Item] {
+        self.check_contiguous();
+        self.container.as_slice()
+    }
+}
+
+unsafe impl<A: ContiguousMut> ContiguousMut for Shadow<A> {
+    fn begin_mut(&mut self) -> *mut Self::Item {
+        self.check_contiguous();
+
+        let begin = self.container.begin_mut();
+        assert_eq!(
+            begin as *const _,
+            self.container.begin(),
+            "ContiguousMut::begin_mut does not agree with Contiguous::begin"
+        );
+
+        begin
+    }
+
+    fn end_mut(&mut self) -> *mut Self::Item {
+        self.check_contiguous();
+
+        let end = self.container.end_mut();
+        assert_eq!(
+            end as *const _,
+            self.container.end(),
+            "ContiguousMut::end_mut does not agree with Contiguous::end"
+        );
+
+        end
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [Self::Item] {
+        self.check_contiguous();
+
+        let len = self.container.base_len();
+        let slice = self.container.as_mut_slice();
+        assert_eq!(
+            slice.len(),
+            len,
+            "ContiguousMut::as_mut_slice returned a slice of length {}, but ContainerTrait::base_len is {}",
+            slice.len(),
+            len
+        );
+
+        slice
+    }
+}
+
+unsafe impl<A: GetUnchecked> GetUnchecked for Shadow<A> {
+    unsafe fn unchecked(&self, index: usize) -> &Self::Item {
+        let len = self.container.base_len();
+        assert!(
+            index < len,
+            "GetUnchecked::unchecked called with index {}, but the length is {}",
+            index,
+            len
+        );
+
+        self.container.unchecked(index)
+    }
+}
+
+unsafe impl<A: GetUncheckedMut> GetUncheckedMut for Shadow<A> {
+    unsafe fn unchecked_mut(&mut self, index: usize) -> &mut Self::Item {
+        let len = self.container.base_len();
+        assert!(
+            index < len,
+            "GetUncheckedMut::unchecked_mut called with index {}, but the length is {}",
+            index,
+            len
+        );
+
+        self.container.unchecked_mut(index)
+    }
+}
+
+/// Asserts the halves returned from a split at `index` of a container of length `len`
+/// are of length `index` and `len - index`.
+fn check_split(method: &str, index: usize, len: usize, lhs: usize, rhs: usize) {
+    assert_eq!(
+        (lhs, rhs),
+        (index, len - index),
+        "SplitUnchecked::{} at index {} of a container of length {} returned halves of length {} and {}",
+        method,
+        index,
+        len,
+        lhs,
+        rhs
+    );
+}
+
+unsafe impl<A> SplitUnchecked for Shadow<A>
+where
+    A: SplitUnchecked,
+    A::Split: ContainerTrait,
+{
+    type Split = A::Split;
+
+    unsafe fn split_unchecked(&self, index: usize) -> (&Self::Split, &Self::Split) {
+        let len = self.container.base_len();
+        assert!(
+            index <= len,
+            "SplitUnchecked::split_unchecked called with index {}, but the length is {}",
+            index,
+            len
+        );
+
+        let (lhs, rhs) = self.container.split_unchecked(index);
+        check_split(
+            "split_unchecked",
+            index,
+            len,
+            lhs.base_len(),
+            rhs.base_len(),
+        );
+
+        (lhs, rhs)
+    }
+}
+
+unsafe impl<A> SplitUncheckedMut for Shadow<A>
+where
+    A: SplitUncheckedMut,
+    A::Split: ContainerTrait,
+{
+    unsafe fn split_unchecked_mut(&mut self, index: usize) -> (&mut Self::Split, &mut Self::Split) {
+        let len = self.container.base_len();
+        assert!(
+            index <= len,
+            "SplitUncheckedMut::split_unchecked_mut called with index {}, but the length is {}",
+            index,
+            len
+        );
+
+        let (lhs, rhs) = self.container.split_unchecked_mut(index);
+        check_split(
+            "split_unchecked_mut",
+            index,
+            len,
+            lhs.base_len(),
+            rhs.base_len(),
+        );
+
+        (lhs, rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::region;
+
+    /// A container with a configurable bug in each of its trait impls.
+    #[derive(Default)]
+    struct Buggy {
+        data: Vec<u32>,
+        extra_len: usize,
+        end_off_by_one: bool,
+        split_off_by_one: bool,
+    }
+
+    impl Buggy {
+        fn new(data: Vec<u32>) -> Self {
+            Self {
+                data,
+                ..Self::default()
+            }
+        }
+    }
+
+    unsafe impl ContainerTrait for Buggy {
+        type Item = u32;
+
+        fn base_len(&self) -> usize {
+            self.data.len() + self.extra_len
+        }
+    }
+
+    unsafe impl Contiguous for Buggy {
+        fn begin(&self) -> *const u32 {
+            self.data.as_ptr()
+        }
+
+        fn end(&self) -> *const u32 {
+            let end = self.data.as_ptr().wrapping_add(self.data.len());
+
+            if self.end_off_by_one {
+                end.wrapping_sub(1)
+            } else {
+                end
+            }
+        }
+
+        fn as_slice(&self) -> &[u32] {
+            &self.data
+        }
+    }
+
+    unsafe impl GetUnchecked for Buggy {
+        unsafe fn unchecked(&self, index: usize) -> &u32 {
+            self.data.get_unchecked(index)
+        }
+    }
+
+    unsafe impl SplitUnchecked for Buggy {
+        type Split = [u32];
+
+        unsafe fn split_unchecked(&self, index: usize) -> (&[u32], &[u32]) {
+            if self.split_off_by_one {
+                self.data.split_at(index - 1)
+            } else {
+                self.data.split_at(index)
+            }
+        }
+    }
+
+    #[test]
+    fn shadow_forwards_correct_impls() {
+        let mut v = vec![1, 2, 3, 4];
+
+        region(Shadow::new(v.as_mut_slice()), |mut s| {
+            let r = s.range().nonempty().unwrap();
+            s.swap(r.first(), r.last());
+
+            let (lhs, rhs) = s.split_at(r.upper_middle());
+            assert_eq!(lhs, [4, 2]);
+            assert_eq!(rhs, [3, 1]);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "ContainerTrait::base_len is 4")]
+    fn shadow_catches_wrong_len() {
+        let buggy = Buggy {
+            extra_len: 1,
+            ..Buggy::new(vec![1, 2, 3])
+        };
+
+        region(Shadow::new(buggy), |s| {
+            let _ = &s[s.range()];
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Contiguous::end does not point 3 elements past")]
+    fn shadow_catches_wrong_end() {
+        let buggy = Buggy {
+            end_off_by_one: true,
+            ..Buggy::new(vec![1, 2, 3])
+        };
+
+        region(Shadow::new(buggy), |s| {
+            let _ = &s[..];
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "returned halves of length 0 and 3")]
+    fn shadow_catches_wrong_split() {
+        let buggy = Buggy {
+            split_off_by_one: true,
+            ..Buggy::new(vec![1, 2, 3])
+        };
+
+        region(Shadow::new(buggy), |s| {
+            let mid = s.range().nonempty().unwrap().upper_middle();
+            let _ = s.split_at(mid);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "called with index 3, but the length is 3")]
+    fn shadow_catches_out_of_bounds_access() {
+        let shadow = Shadow::new(Buggy::new(vec![1, 2, 3]));
+
+        unsafe {
+            shadow.unchecked(3);
+        }
+    }
+}
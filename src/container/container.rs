@@ -1,15 +1,52 @@
+use core::{
+    cmp::Ordering,
+    iter,
+    mem::{ManuallyDrop, MaybeUninit},
+    num::NonZeroUsize,
+    ptr,
+};
+
+use super::distance::Distance;
+use super::error::{LengthMismatch, OutOfBounds, RangeError};
+use super::interpolable::Interpolable;
+use super::iter::{
+    BrandedWindows, Chunks, GroupBy, IndicesWhere, RChunks, SplitBy, SplitByNonEmpty, Windows,
+};
 use super::traits::{
     ContainerTrait, Contiguous, ContiguousMut, GetUnchecked, GetUncheckedMut, SplitUnchecked,
     SplitUncheckedMut,
 };
 
 use crate::core::{
-    index::Index,
-    proof::NonEmpty,
-    range::Range,
-    seal::{Contract, Seal},
+    index::{Edge, Index},
+    proof::{ExactLen, LenEq, NonEmpty, Transferable, Unknown},
+    range::{Range, RangeIter},
+    seal::{Contract, Seal, Signed},
+    translator::Translator,
 };
 
+/// Ranges of at most this length are sorted by `Container::small_sort`.
+const SMALL_SORT_THRESHOLD: usize = 24;
+
+/// Ranges of at least this length take a recursive pseudo-median as their pivot.
+const PSEUDO_MEDIAN_THRESHOLD: usize = 64;
+
+/// A hole left by moving an element out during insertion sort.
+///
+/// On drop, the element at `src` is moved into `dest`,
+/// such that the hole is filled even if the comparator panics.
+struct InsertionHole<T> {
+    src: *const T,
+    dest: *mut T,
+}
+
+impl<T> Drop for InsertionHole<T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        unsafe { ptr::copy_nonoverlapping(self.src, self.dest, 1) };
+    }
+}
+
 /// A container is a generic container over type A (array).
 /// it also carries a Contract (C) with it,
 /// which it uses to sign contracts between the container,
@@ -59,17 +96,376 @@ where
         self.container.base_len()
     }
 
+    /// Returns the length of the container, which is proven to be non-zero by `proof`.
+    #[inline(always)]
+    pub fn len_nonzero(&self, _proof: Range<C, NonEmpty>) -> NonZeroUsize {
+        // `_proof` lies within the container, so the container is at least as long.
+        unsafe { NonZeroUsize::new_unchecked(self.len()) }
+    }
+
+    /// Returns a reference to the element at `index`,
+    /// or None if `index` is out of bounds.
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Option<&T>
+    where
+        A: GetUnchecked,
+    {
+        if index < self.len() {
+            unsafe { Some(self.container.unchecked(index)) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the element at `index`,
+    /// or None if `index` is out of bounds.
+    #[inline(always)]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T>
+    where
+        A: GetUncheckedMut,
+    {
+        if index < self.len() {
+            unsafe { Some(self.container.unchecked_mut(index)) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the first element within `r`.
+    #[inline(always)]
+    pub fn first_of(&self, r: Range<C, NonEmpty>) -> &T
+    where
+        A: GetUnchecked,
+    {
+        &self[r.first()]
+    }
+
+    /// Returns a reference to the last element within `r`.
+    #[inline(always)]
+    pub fn last_of(&self, r: Range<C, NonEmpty>) -> &T
+    where
+        A: GetUnchecked,
+    {
+        &self[r.last()]
+    }
+
+    /// Returns a mutable reference to the first element within `r`.
+    #[inline(always)]
+    pub fn first_of_mut(&mut self, r: Range<C, NonEmpty>) -> &mut T
+    where
+        A: GetUncheckedMut,
+    {
+        &mut self[r.first()]
+    }
+
+    /// Returns a mutable reference to the last element within `r`.
+    #[inline(always)]
+    pub fn last_of_mut(&mut self, r: Range<C, NonEmpty>) -> &mut T
+    where
+        A: GetUncheckedMut,
+    {
+        &mut self[r.last()]
+    }
+
+    /// Returns a reference to the first element of the container,
+    /// or None if the container is empty.
+    #[inline(always)]
+    pub fn first(&self) -> Option<&T>
+    where
+        A: GetUnchecked,
+    {
+        self.range().nonempty().map(|r| self.first_of(r))
+    }
+
+    /// Returns a reference to the last element of the container,
+    /// or None if the container is empty.
+    #[inline(always)]
+    pub fn last(&self) -> Option<&T>
+    where
+        A: GetUnchecked,
+    {
+        self.range().nonempty().map(|r| self.last_of(r))
+    }
+
+    /// Returns a reference to the first element of the container
+    /// together with the range `1..len`, or None if the container is empty.
+    ///
+    /// Unlike [`split_first`](Container::split_first), the tail keeps the brand
+    /// of this container, so existing indices remain usable alongside it.
+    #[inline(always)]
+    pub fn first_and_tail(&self) -> Option<(&T, Range<C>)>
+    where
+        A: GetUnchecked,
+    {
+        self.range()
+            .nonempty()
+            .map(|r| (self.first_of(r), r.tail()))
+    }
+
+    /// Returns a mutable reference to the first element of the container
+    /// together with the range `1..len`, or None if the container is empty.
+    #[inline(always)]
+    pub fn first_and_tail_mut(&mut self) -> Option<(&mut T, Range<C>)>
+    where
+        A: GetUncheckedMut,
+    {
+        match self.range().nonempty() {
+            Some(r) => Some((self.first_of_mut(r), r.tail())),
+            None => None,
+        }
+    }
+
+    /// Returns the elements of the container as a slice.
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[T]
+    where
+        A: Contiguous,
+    {
+        self.container.as_slice()
+    }
+
+    /// Returns the elements of the container as a mutable slice.
+    #[inline(always)]
+    pub fn as_mut_slice(&mut self) -> &mut [T]
+    where
+        A: ContiguousMut,
+    {
+        self.container.as_mut_slice()
+    }
+
+    /// Returns a raw pointer to the first element of the container.
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *const T
+    where
+        A: Contiguous,
+    {
+        self.container.begin()
+    }
+
+    /// Returns a raw mutable pointer to the first element of the container.
+    #[inline(always)]
+    pub fn as_mut_ptr(&mut self) -> *mut T
+    where
+        A: ContiguousMut,
+    {
+        self.container.as_mut_slice().as_mut_ptr()
+    }
+
+    /// Returns the raw pointers spanning the elements within `r`,
+    /// the end pointer being one past the last element.
+    ///
+    /// The pointers are valid for reads for as long as the container is not mutated or moved.
+    #[inline(always)]
+    pub fn ptr_range<P>(&self, r: Range<C, P>) -> ops::Range<*const T>
+    where
+        A: Contiguous,
+    {
+        let begin = self.as_ptr();
+
+        unsafe { begin.add(r.start())..begin.add(r.end()) }
+    }
+
+    /// Returns the raw mutable pointers spanning the elements within `r`,
+    /// the end pointer being one past the last element.
+    ///
+    /// The pointers are derived from a unique borrow of the whole container,
+    /// and are valid for reads and writes until the container is accessed through any other path.
+    /// Writing through them while a reference into the container is alive is undefined behaviour.
+    #[inline(always)]
+    pub fn mut_ptr_range<P>(&mut self, r: Range<C, P>) -> ops::Range<*mut T>
+    where
+        A: ContiguousMut,
+    {
+        let begin = self.as_mut_ptr();
+
+        unsafe { begin.add(r.start())..begin.add(r.end()) }
+    }
+
+    /// Returns an iterator over the elements of the container.
+    #[inline(always)]
+    pub fn iter(&self) -> core::slice::Iter<'_, T>
+    where
+        A: Contiguous,
+    {
+        self.container.as_slice().iter()
+    }
+
+    /// Returns an iterator over the elements of the container, that allows modifying each element.
+    #[inline(always)]
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, T>
+    where
+        A: ContiguousMut,
+    {
+        self.container.as_mut_slice().iter_mut()
+    }
+
+    /// Returns an iterator over the elements of the container, paired with their index.
+    #[inline(always)]
+    pub fn iter_indexed(&self) -> iter::Zip<RangeIter<C>, core::slice::Iter<'_, T>>
+    where
+        A: Contiguous,
+    {
+        self.range().into_iter().zip(self.iter())
+    }
+
+    /// Returns an iterator over the elements of the container, paired with their index,
+    /// that allows modifying each element.
+    #[inline(always)]
+    pub fn iter_mut_indexed(&mut self) -> iter::Zip<RangeIter<C>, core::slice::IterMut<'_, T>>
+    where
+        A: ContiguousMut,
+    {
+        let range = self.range();
+
+        range.into_iter().zip(self.iter_mut())
+    }
+
+    /// Returns a proof that `self` and `other` are of equal length,
+    /// or None if they are not.
+    #[inline(always)]
+    pub fn len_eq<D, B>(&self, other: &Container<D, B>) -> Option<LenEq<C, D>>
+    where
+        D: for<'s> Contract<'s>,
+        B: ContainerTrait,
+    {
+        if self.len() == other.len() {
+            unsafe { Some(LenEq::new()) }
+        } else {
+            None
+        }
+    }
+
+    /// Checks `index` is within the bounds of the container once,
+    /// and returns it as an index which can be used without any further checks.
+    #[inline(always)]
+    pub fn vet(&self, index: usize) -> Result<Index<C>, OutOfBounds> {
+        if index < self.len() {
+            unsafe { Ok(Index::new(index)) }
+        } else {
+            Err(OutOfBounds::new(index, self.len()))
+        }
+    }
+
+    /// Returns the elements within the untrusted range `r`,
+    /// or None if `r` is inverted or extends past the end of the container.
+    #[inline(always)]
+    pub fn get_slice(&self, r: ops::Range<usize>) -> Option<&[T]>
+    where
+        A: Contiguous,
+    {
+        let r = self.vet_range(r.start, r.end).ok()?;
+
+        Some(&self[r])
+    }
+
+    /// Returns the elements within the untrusted range `r` mutably,
+    /// or None if `r` is inverted or extends past the end of the container.
+    #[inline(always)]
+    pub fn get_slice_mut(&mut self, r: ops::Range<usize>) -> Option<&mut [T]>
+    where
+        A: ContiguousMut,
+    {
+        let r = self.vet_range(r.start, r.end).ok()?;
+
+        Some(&mut self[r])
+    }
+
+    /// Checks `edge` lies within `0..=len` once,
+    /// and returns it as an edge which can be used to split the container without any further checks.
+    #[inline(always)]
+    pub fn vet_edge(&self, edge: usize) -> Result<Edge<C>, OutOfBounds> {
+        if edge <= self.len() {
+            unsafe { Ok(Index::new(edge)) }
+        } else {
+            Err(OutOfBounds::new(edge, self.len()))
+        }
+    }
+
+    /// Returns the edge before the first element of the container.
+    #[inline(always)]
+    pub fn start_edge(&self) -> Edge<C> {
+        unsafe { Index::new(0) }
+    }
+
+    /// Returns the edge after the last element of the container.
+    #[inline(always)]
+    pub fn end_edge(&self) -> Edge<C> {
+        unsafe { Index::new(self.len()) }
+    }
+
+    /// Checks `start` and `end` make up a valid range within the container once,
+    /// and returns it as a range which can be used without any further checks.
+    #[inline(always)]
+    pub fn vet_range(&self, start: usize, end: usize) -> Result<Range<C>, RangeError> {
+        if start > end {
+            Err(RangeError::Inverted { start, end })
+        } else if end > self.len() {
+            Err(RangeError::EndOutOfBounds {
+                end,
+                len: self.len(),
+            })
+        } else {
+            unsafe { Ok(Range::from_unknown(start, end)) }
+        }
+    }
+
+    /// Checks `start` and `end` make up a valid, non-empty range within the container once,
+    /// and returns it as a NonEmpty range which can be used without any further checks.
+    #[inline(always)]
+    pub fn vet_range_nonempty(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> Result<Range<C, NonEmpty>, RangeError> {
+        self.vet_range(start, end)?
+            .nonempty()
+            .ok_or(RangeError::Empty { at: start })
+    }
+
+    /// Returns the range of the first `n` elements of the container,
+    /// or None if `n` is 0 or exceeds the length of the container.
+    #[inline(always)]
+    pub fn prefix(&self, n: usize) -> Option<Range<C, NonEmpty>> {
+        self.vet_range(0, n).ok()?.nonempty()
+    }
+
+    /// Returns the range of the last `n` elements of the container,
+    /// or None if `n` is 0 or exceeds the length of the container.
+    #[inline(always)]
+    pub fn suffix(&self, n: usize) -> Option<Range<C, NonEmpty>> {
+        let start = self.len().checked_sub(n)?;
+
+        self.vet_range(start, self.len()).ok()?.nonempty()
+    }
+
     /// Returns a range into the container.
     #[inline(always)]
     pub fn range(&self) -> Range<C> {
         unsafe { Range::from_unknown(0, self.len()) }
     }
 
+    /// Returns the index of the first element of the container,
+    /// or None if the container is empty.
+    #[inline(always)]
+    pub fn first_index(&self) -> Option<Index<C>> {
+        self.range().nonempty().map(|r| r.first())
+    }
+
+    /// Returns the index of the last element of the container,
+    /// or None if the container is empty.
+    #[inline(always)]
+    pub fn last_index(&self) -> Option<Index<C>> {
+        self.range().nonempty().map(|r| r.last())
+    }
+
     /// Returns 2 ranges into the container,
     /// one from `0..index`, the other from `index..self.len()`.
     /// Proof `P` of the length transfers to the latter end.
     #[inline(always)]
-    pub fn split_at_index<P>(&self, index: Index<C, P>) -> (Range<C>, Range<C, P>) {
+    pub fn split_at_index<P>(&self, index: Index<C, P>) -> (Range<C>, Range<C, P>)
+    where
+        P: Transferable,
+    {
         unsafe {
             (
                 Range::from_unknown(0, index.integer()),
@@ -78,23 +474,56 @@ where
         }
     }
 
+    /// Splits the container around `index`, which is excluded from both halves.
+    /// The latter half starts directly after `index`, and may be empty.
+    #[inline(always)]
+    pub fn split_around(&self, index: Index<C>) -> (Range<C>, Index<C>, Range<C>) {
+        unsafe {
+            (
+                Range::from_unknown(0, index.integer()),
+                index,
+                Range::from_unknown(index.integer() + 1, self.len()),
+            )
+        }
+    }
+
     /// Swaps the element at index `a` with the element at index `b`.
     #[inline(always)]
     pub fn swap(&mut self, a: Index<C>, b: Index<C>)
     where
         A: GetUncheckedMut,
     {
-        use core::ptr;
-
+        // Every element is borrowed on its own and only once at a time,
+        // as a pointer to `a` would be invalidated by reborrowing `self` for `b`.
+        // Nothing in between the reads and the writes can panic, so nothing is dropped twice.
         unsafe {
-            let self_mut = self as *mut Self;
-            let pa: *mut _ = &mut (*self_mut)[a];
-            let pb: *mut _ = &mut (*self_mut)[b];
+            let value_a = ptr::read(&self[a]);
+            let value_b = ptr::read(&self[b]);
 
-            ptr::swap(pa, pb);
+            ptr::write(&mut self[a], value_b);
+            ptr::write(&mut self[b], value_a);
         }
     }
 
+    /// Replaces the element at `index` with `value`, returning the old element.
+    #[inline(always)]
+    pub fn replace(&mut self, index: Index<C>, value: T) -> T
+    where
+        A: GetUncheckedMut,
+    {
+        core::mem::replace(&mut self[index], value)
+    }
+
+    /// Replaces the element at `index` with its default value, returning the old element.
+    #[inline(always)]
+    pub fn take(&mut self, index: Index<C>) -> T
+    where
+        T: Default,
+        A: GetUncheckedMut,
+    {
+        self.replace(index, T::default())
+    }
+
     /// Scans the range after `index`, in order from the lower indices towards the higher.
     /// While the closure returns `true`, the scan continue's, and the scanned element is included in the range.
     ///
@@ -121,6 +550,32 @@ where
         unsafe { Range::from_nonempty(index.integer(), end) }
     }
 
+    /// Scans the range after `index`, in order from the lower indices towards the higher,
+    /// passing each element mutably to the closure.
+    /// While the closure returns `true`, the scan continue's, and the scanned element is included in the range.
+    ///
+    /// The resulting range always includes `index` in the range.
+    #[inline(always)]
+    pub fn scan_from_mut<F>(&mut self, index: Index<C>, mut f: F) -> Range<C, NonEmpty>
+    where
+        F: FnMut(&mut T) -> bool,
+        A: ContiguousMut<Item = T>,
+    {
+        let mut end = index.integer();
+
+        for item in &mut self[index.after()..] {
+            if !f(item) {
+                break;
+            }
+
+            end += 1;
+        }
+
+        end += 1;
+
+        unsafe { Range::from_nonempty(index.integer(), end) }
+    }
+
     /// Scans the range before `index, in order from the higher indices towards the lower.
     /// While the closure returns `true`, the scan continue's, and the scanned element is included in the range.
     ///
@@ -146,227 +601,5456 @@ where
             Range::from_nonempty(start, index.integer() + 1)
         }
     }
-}
 
-impl<C: for<'s> Contract<'s>, A, T> Container<C, A>
-where
-    A: SplitUnchecked<Item = T>,
-{
-    #[inline(always)]
-    pub fn split_first(&self) -> Option<(&T, &<A as SplitUnchecked>::Split)>
+    /// Returns the maximal run of elements around `index`,
+    /// for which `eq` returns `true` when compared with the element at `index`.
+    ///
+    /// The resulting range always includes `index` in the range.
+    #[inline]
+    pub fn run_at<F>(&self, index: Index<C>, mut eq: F) -> Range<C, NonEmpty>
     where
-        <A as SplitUnchecked>::Split: GetUnchecked<Item = T>,
+        F: FnMut(&T, &T) -> bool,
+        A: Contiguous<Item = T> + GetUnchecked,
     {
-        // The bound on <A as SplitUnchecked>::Split allows the `unchecked(0)` call.
-        unsafe {
-            if !self.is_empty() {
-                let split = self.split_at(Index::new(1));
+        let item = &self[index];
 
-                let (lhs, rhs) = split;
+        let before = self.scan_from_rev(index, |other| eq(other, item));
+        let after = self.scan_from(index, |other| eq(item, other));
 
-                Some((lhs.unchecked(0), rhs))
-            } else {
-                None
-            }
-        }
+        unsafe { Range::from_nonempty(before.start(), after.end()) }
     }
 
-    /// Divides one container into two at `index`.
-    /// The first will contain all indices from `[0, index)` and the second will contain all indices from
-    /// [mid, len).
+    /// Scans the range after `index`, in order from the lower indices towards the higher.
+    /// While the closure returns `true`, the scan continue's, and the scanned element is included in the range.
+    ///
+    /// Unlike [`Container::scan_from`], `index` itself is not included, so the resulting range may be empty.
     #[inline(always)]
-    pub fn split_at(
-        &self,
-        index: Index<C>,
-    ) -> (&<A as SplitUnchecked>::Split, &<A as SplitUnchecked>::Split) {
-        unsafe { self.container.split_unchecked(index.integer()) }
-    }
-}
+    pub fn scan_after<'b, F>(&'b self, index: Index<C>, mut f: F) -> Range<C>
+    where
+        F: FnMut(&'b T) -> bool,
+        T: 'b,
+        A: Contiguous<Item = T>,
+    {
+        let start = index.integer() + 1;
+        let mut end = start;
 
-impl<C: for<'s> Contract<'s>, A, T> Container<C, A>
-where
-    A: SplitUncheckedMut<Item = T>,
-{
+        for item in &self[index.after()..] {
+            if !f(item) {
+                break;
+            }
+
+            end += 1;
+        }
+
+        unsafe { Range::from_unknown(start, end) }
+    }
+
+    /// Scans the range before `index`, in order from the higher indices towards the lower.
+    /// While the closure returns `true`, the scan continue's, and the scanned element is included in the range.
+    ///
+    /// Unlike [`Container::scan_from_rev`], `index` itself is not included, so the resulting range may be empty.
     #[inline(always)]
-    pub fn split_first_mut(&mut self) -> Option<(&mut T, &mut <A as SplitUnchecked>::Split)>
+    pub fn scan_before<'b, F>(&'b self, index: Index<C>, mut f: F) -> Range<C>
     where
-        <A as SplitUnchecked>::Split: GetUncheckedMut<Item = T>,
+        F: FnMut(&'b T) -> bool,
+        T: 'b,
+        A: Contiguous<Item = T>,
     {
-        // The bound on <A as SplitUnchecked>::Split allows the `unchecked_mut(0)` call.
-        unsafe {
-            if !self.is_empty() {
-                let split = self.split_at_mut(Index::new(1));
-
-                let (lhs, rhs) = split;
+        let end = index.integer();
+        let mut start = end;
 
-                Some((lhs.unchecked_mut(0), rhs))
-            } else {
-                None
+        for item in self[..index].iter().rev() {
+            if !f(item) {
+                break;
             }
+
+            start -= 1;
         }
+
+        unsafe { Range::from_unknown(start, end) }
     }
-    /// Divides one mutable container into two at `index`.
-    /// The first will contain all indices from `[0, index)` and the second will contain all indices from
-    /// [mid, len).
+
+    /// Scans `r`, in order from the lower indices towards the higher.
+    /// While the closure returns `true`, the scan continue's, and the scanned element is included in the range.
+    ///
+    /// The resulting range is the longest prefix of `r` of which every element satisfies the closure,
+    /// and never extends past `r.end()`.
     #[inline(always)]
-    pub fn split_at_mut(
-        &mut self,
-        index: Index<C>,
-    ) -> (
-        &mut <A as SplitUnchecked>::Split,
-        &mut <A as SplitUnchecked>::Split,
-    ) {
-        unsafe { self.container.split_unchecked_mut(index.integer()) }
-    }
-}
+    pub fn scan_range<'b, P, F>(&'b self, r: Range<C, P>, mut f: F) -> Range<C>
+    where
+        F: FnMut(&'b T) -> bool,
+        T: 'b,
+        A: Contiguous<Item = T>,
+    {
+        let mut end = r.start();
 
-use core::ops;
+        for item in &self[r] {
+            if !f(item) {
+                break;
+            }
 
-// &self[i]
-impl<C: for<'s> Contract<'s>, A> ops::Index<Index<C>> for Container<C, A>
-where
-    A: GetUnchecked,
-{
-    type Output = A::Item;
+            end += 1;
+        }
 
-    #[inline(always)]
-    fn index(&self, index: Index<C>) -> &Self::Output {
-        unsafe { self.container.unchecked(index.integer()) }
+        debug_assert!(r.start() <= end && end <= r.end());
+
+        unsafe { Range::from_unknown(r.start(), end) }
     }
-}
 
-// &mut self[i]
-impl<C: for<'s> Contract<'s>, A> ops::IndexMut<Index<C>> for Container<C, A>
-where
-    A: GetUncheckedMut,
-{
-    #[inline(always)]
-    fn index_mut(&mut self, index: Index<C>) -> &mut Self::Output {
-        unsafe { self.container.unchecked_mut(index.integer()) }
+    /// Returns `true` if all elements within `r` for which `pred` returns `true`
+    /// precede all elements for which `pred` returns `false`.
+    ///
+    /// An empty range is always partitioned.
+    #[inline]
+    pub fn is_partitioned<P, F>(&self, r: Range<C, P>, mut pred: F) -> bool
+    where
+        F: FnMut(&T) -> bool,
+        A: Contiguous<Item = T>,
+    {
+        let mut iter = self[r].iter();
+
+        iter.all(&mut pred) || !iter.any(pred)
     }
-}
 
-// &self[r]
-impl<C: for<'s> Contract<'s>, A, T, P> ops::Index<Range<C, P>> for Container<C, A>
-where
-    A: Contiguous<Item = T>,
-{
-    type Output = [T];
+    /// Returns the index of the first element within `r` for which `pred` returns `false`.
+    ///
+    /// Returns None if the range is not partitioned by `pred` (see [`Container::is_partitioned`]),
+    /// or if `pred` holds for every element within `r`.
+    #[inline]
+    pub fn partition_boundary<P, F>(&self, r: Range<C, P>, mut pred: F) -> Option<Index<C>>
+    where
+        F: FnMut(&T) -> bool,
+        A: Contiguous<Item = T>,
+    {
+        let mut iter = self[r].iter();
 
-    #[inline(always)]
-    fn index(&self, r: Range<C, P>) -> &Self::Output {
-        use core::slice;
+        let offset = iter.position(|item| !pred(item))?;
 
-        unsafe { slice::from_raw_parts(self.container.begin().offset(r.start() as isize), r.len()) }
+        if iter.any(pred) {
+            None
+        } else {
+            unsafe { Some(Index::new(r.start() + offset)) }
+        }
     }
-}
 
-// &mut self[r]
-impl<C: for<'s> Contract<'s>, A, P> ops::IndexMut<Range<C, P>> for Container<C, A>
-where
-    A: ContiguousMut,
-{
-    #[inline(always)]
-    fn index_mut(&mut self, r: Range<C, P>) -> &mut Self::Output {
-        use core::slice;
+    /// Reorders the elements within `r` into three contiguous groups,
+    /// according to the [`Ordering`] `cmp` returns for each element:
+    /// first all `Less` elements, then all `Equal` elements, and lastly all `Greater` elements.
+    ///
+    /// Returns the three groups as ranges, which together tile `r` exactly.
+    /// The order of the elements within each group is unspecified.
+    #[inline]
+    pub fn partition3<P, F>(&mut self, r: Range<C, P>, mut cmp: F) -> (Range<C>, Range<C>, Range<C>)
+    where
+        F: FnMut(&T) -> Ordering,
+        A: GetUncheckedMut,
+    {
+        // The elements in `r.start()..less` are less, those in `less..unseen.start()` equal,
+        // and those in `unseen.end()..r.end()` greater.
+        let mut less = r.start();
+        let mut unseen = r.forget_proof();
+
+        while let Some(nonempty) = unseen.nonempty() {
+            let current = nonempty.first();
+
+            match cmp(&self[current]) {
+                Ordering::Less => {
+                    // `less` is at most `current`, so it lies within `r`.
+                    let first_equal = unsafe { Index::new(less) };
+
+                    if first_equal != current {
+                        self.swap(first_equal, current);
+                    }
 
+                    less += 1;
+                    unseen = nonempty.tail();
+                }
+                Ordering::Equal => unseen = nonempty.tail(),
+                Ordering::Greater => {
+                    let last = nonempty.last();
+
+                    if last != current {
+                        self.swap(current, last);
+                    }
+
+                    unseen = nonempty.head();
+                }
+            }
+        }
+
+        // `r.start() <= less <= unseen.start() == unseen.end() <= r.end()`.
         unsafe {
-            slice::from_raw_parts_mut(
-                self.container.begin_mut().offset(r.start() as isize),
-                r.len(),
+            (
+                Range::from_unknown(r.start(), less),
+                Range::from_unknown(less, unseen.start()),
+                Range::from_unknown(unseen.end(), r.end()),
             )
         }
     }
-}
 
-// &self[i..]
-impl<C: for<'s> Contract<'s>, A, T, P> ops::Index<ops::RangeFrom<Index<C, P>>> for Container<C, A>
-where
-    A: Contiguous<Item = T>,
-{
-    type Output = [T];
+    /// Returns the edge within `r` at which `pred` flips from `true` to `false`,
+    /// assuming `r` is partitioned by `pred`.
+    ///
+    /// If every element satisfies `pred`, this is `r.end()`, if none do, it is `r.start()`.
+    /// The returned index can be passed to [`split_at_index`](Container::split_at_index).
+    #[inline]
+    pub fn partition_point<P, F>(&self, r: Range<C, P>, mut pred: F) -> Index<C, Unknown>
+    where
+        F: FnMut(&T) -> bool,
+        A: GetUnchecked,
+    {
+        let mut range = r.forget_proof();
 
-    #[inline(always)]
-    fn index(&self, r: ops::RangeFrom<Index<C, P>>) -> &Self::Output {
-        use core::slice;
+        while let Some(nonempty) = range.nonempty() {
+            let mid = nonempty.upper_middle();
+            let (lhs, rhs) = nonempty.split_index(mid);
 
-        let i = r.start.integer();
+            range = if pred(&self[mid]) { rhs.tail() } else { lhs };
+        }
 
-        unsafe { slice::from_raw_parts(self.container.begin().offset(i as isize), self.len() - i) }
+        unsafe { Index::new(range.start()) }
     }
-}
 
-// &mut self[i..]
-impl<C: for<'s> Contract<'s>, A, P> ops::IndexMut<ops::RangeFrom<Index<C, P>>> for Container<C, A>
-where
-    A: ContiguousMut,
-{
-    #[inline(always)]
-    fn index_mut(&mut self, r: ops::RangeFrom<Index<C, P>>) -> &mut Self::Output {
-        use core::slice;
+    /// Returns the index of the element within the sorted range `r` that is closest to `x`.
+    ///
+    /// The distance between two elements is given by [`Distance`], which does not overflow.
+    /// When two elements are equally close to `x`, the one at the lower index is returned.
+    #[inline]
+    pub fn binary_search_nearest(&self, r: Range<C, NonEmpty>, x: &T) -> Index<C>
+    where
+        T: Ord + Distance,
+        A: GetUnchecked,
+    {
+        match self.nearest_candidates(r, |item| item < x) {
+            Ok(only) => only,
+            Err((lower, upper)) if self[lower].distance(x) <= self[upper].distance(x) => lower,
+            Err((_, upper)) => upper,
+        }
+    }
 
-        let i = r.start.integer();
+    /// Returns the index of the element within `r` whose key is closest to `key`,
+    /// assuming `r` is sorted by the key.
+    ///
+    /// Closeness is defined by `distance`, which is only ever called
+    /// with the key of an element as the first argument, and `key` as the second.
+    /// When two elements are equally close to `key`, the one at the lower index is returned.
+    #[inline]
+    pub fn binary_search_nearest_by_key<K, D, F, G>(
+        &self,
+        r: Range<C, NonEmpty>,
+        key: &K,
+        mut f: F,
+        mut distance: G,
+    ) -> Index<C>
+    where
+        F: FnMut(&T) -> K,
+        G: FnMut(&K, &K) -> D,
+        K: Ord,
+        D: Ord,
+        A: GetUnchecked,
+    {
+        match self.nearest_candidates(r, |item| f(item) < *key) {
+            Ok(only) => only,
+            Err((lower, upper)) => {
+                if distance(&f(&self[lower]), key) <= distance(&f(&self[upper]), key) {
+                    lower
+                } else {
+                    upper
+                }
+            }
+        }
+    }
 
-        unsafe {
-            slice::from_raw_parts_mut(
-                self.container.begin_mut().offset(i as isize),
-                self.len() - i,
-            )
+    /// Returns the candidates for the element nearest to the edge of `is_less` within `r`:
+    /// `Ok` with the only candidate at either end of `r`,
+    /// or `Err` with the elements directly before and at the edge.
+    fn nearest_candidates<F>(
+        &self,
+        r: Range<C, NonEmpty>,
+        is_less: F,
+    ) -> Result<Index<C>, (Index<C>, Index<C>)>
+    where
+        F: FnMut(&T) -> bool,
+        A: GetUnchecked,
+    {
+        let edge = self.partition_point(r, is_less);
+
+        match r.contains(edge.integer()) {
+            None => Ok(r.last()),
+            Some(upper) if upper == r.first() => Ok(upper),
+            Some(upper) => Err((unsafe { Index::new(upper.integer() - 1) }, upper)),
         }
     }
-}
 
-// &self[..i]
-impl<C: for<'s> Contract<'s>, A, T, P> ops::Index<ops::RangeTo<Index<C, P>>> for Container<C, A>
-where
-    A: Contiguous<Item = T>,
-{
-    type Output = [T];
+    /// Searches the sorted range `r` for `x`, probing at the position
+    /// estimated by interpolating between the first and last element of the remaining range.
+    ///
+    /// On success, returns Ok with the index of a matching element.
+    /// On failure, returns Err with the index `x` could be inserted at while keeping the container sorted.
+    ///
+    /// Every other probe bisects the remaining range,
+    /// such that the worst case is still a logarithmic number of probes.
+    #[inline]
+    pub fn interpolation_search(&self, r: Range<C, NonEmpty>, x: &T) -> Result<Index<C>, usize>
+    where
+        T: PartialOrd + Interpolable,
+        A: GetUnchecked,
+    {
+        let (mut lo, mut hi) = (r.start(), r.end());
+        let key = x.interpolation_key();
+        let mut bisect = false;
 
-    #[inline(always)]
-    fn index(&self, r: ops::RangeTo<Index<C, P>>) -> &Self::Output {
-        use core::slice;
+        // Invariant: r.start() <= lo <= hi <= r.end(), and `x` can only be found within [lo, hi).
+        while lo < hi {
+            let (first, last) = unsafe { (Index::new(lo), Index::new(hi - 1)) };
 
-        let i = r.end.integer();
+            if *x < self[first] {
+                return Err(lo);
+            }
+
+            if *x > self[last] {
+                return Err(hi);
+            }
+
+            let (low, high) = (
+                self[first].interpolation_key(),
+                self[last].interpolation_key(),
+            );
 
-        unsafe { slice::from_raw_parts(self.container.begin(), i) }
+            let offset = if bisect {
+                (hi - lo) / 2
+            } else if low < high {
+                // `x` lies within [low, high], so the fraction lies within [0, 1],
+                // except when the keys are lossy or NaN, hence the clamp below.
+                ((key - low) / (high - low) * (hi - 1 - lo) as f64) as usize
+            } else {
+                0
+            };
+
+            bisect = !bisect;
+
+            let probe = unsafe { Index::new(lo + core::cmp::min(offset, hi - 1 - lo)) };
+
+            if self[probe] < *x {
+                lo = probe.integer() + 1;
+            } else if self[probe] == *x {
+                return Ok(probe);
+            } else {
+                hi = probe.integer();
+            }
+        }
+
+        Err(lo)
     }
-}
 
-// &mut self[i..]
-impl<C: for<'s> Contract<'s>, A, P> ops::IndexMut<ops::RangeTo<Index<C, P>>> for Container<C, A>
-where
-    A: ContiguousMut,
-{
-    #[inline(always)]
-    fn index_mut(&mut self, r: ops::RangeTo<Index<C, P>>) -> &mut Self::Output {
-        use core::slice;
+    /// Reverses the order of the elements within `r`.
+    ///
+    /// Empty and single element ranges are left untouched.
+    #[inline]
+    pub fn reverse<P>(&mut self, r: Range<C, P>)
+    where
+        A: GetUncheckedMut,
+    {
+        let mut indices = r.into_iter();
 
-        let i = r.end.integer();
+        while let (Some(front), Some(back)) = (indices.next(), indices.next_back()) {
+            self.swap(front, back);
+        }
+    }
+
+    /// Rearranges the elements within `r` into the next permutation according to `less`.
+    /// Returns `false` and reverses `r` if it already was the last permutation.
+    #[inline]
+    pub(crate) fn permute<P, F>(&mut self, r: Range<C, P>, mut less: F) -> bool
+    where
+        F: FnMut(&T, &T) -> bool,
+        A: GetUncheckedMut,
+    {
+        let r = match r.nonempty() {
+            Some(r) => r,
+            None => return false,
+        };
+
+        // Every index is within `r`, and `i - 1` is only formed when `i > r.start()`.
+        unsafe {
+            // Find the start of the longest non-ascending suffix.
+            let mut i = r.last().integer();
+            while i > r.start() && !less(&self[Index::new(i - 1)], &self[Index::new(i)]) {
+                i -= 1;
+            }
 
-        unsafe { slice::from_raw_parts_mut(self.container.begin_mut(), i) }
+            if i == r.start() {
+                self.reverse(r);
+                return false;
+            }
+
+            // The suffix contains at least one element greater than the pivot, namely at `i`.
+            let pivot = Index::new(i - 1);
+            let mut successor = r.last();
+            while !less(&self[pivot], &self[successor]) {
+                successor = Index::new(successor.integer() - 1);
+            }
+
+            self.swap(pivot, successor);
+            self.reverse(Range::<C>::from_unknown(i, r.end()));
+        }
+
+        true
     }
-}
 
-// &self[..]
-impl<C: for<'s> Contract<'s>, A, T> ops::Index<ops::RangeFull> for Container<C, A>
-where
-    A: Contiguous<Item = T>,
-{
-    type Output = [T];
+    /// Rearranges the elements within `r` into the lexicographically next permutation.
+    ///
+    /// Returns `true` if such a permutation exists. Otherwise,
+    /// returns `false` and rearranges `r` into the first permutation (sorted ascending).
+    /// Ranges of length 0 and 1 have no next permutation.
+    #[inline]
+    pub fn next_permutation<P>(&mut self, r: Range<C, P>) -> bool
+    where
+        T: Ord,
+        A: GetUncheckedMut,
+    {
+        self.permute(r, |a, b| a < b)
+    }
 
-    #[inline(always)]
-    fn index(&self, _: ops::RangeFull) -> &Self::Output {
-        self.container.as_slice()
+    /// Rearranges the elements within `r` into the lexicographically previous permutation.
+    ///
+    /// Returns `true` if such a permutation exists. Otherwise,
+    /// returns `false` and rearranges `r` into the last permutation (sorted descending).
+    /// Ranges of length 0 and 1 have no previous permutation.
+    #[inline]
+    pub fn prev_permutation<P>(&mut self, r: Range<C, P>) -> bool
+    where
+        T: Ord,
+        A: GetUncheckedMut,
+    {
+        self.permute(r, |a, b| a > b)
     }
-}
 
-// &mut self[..]
-impl<C: for<'s> Contract<'s>, A> ops::IndexMut<ops::RangeFull> for Container<C, A>
-where
-    A: ContiguousMut,
+    /// Swaps the elements within range `a` with the elements within range `b`.
+    ///
+    /// # Panics
+    /// Panics if the ranges differ in length, or if they overlap.
+    #[inline]
+    pub fn swap_ranges(&mut self, a: Range<C, NonEmpty>, b: Range<C, NonEmpty>)
+    where
+        A: ContiguousMut,
+    {
+        assert!(
+            a.len() == b.len(),
+            "swap_ranges: ranges differ in length ({} and {})",
+            a.len(),
+            b.len()
+        );
+
+        assert!(
+            a.end() <= b.start() || b.end() <= a.start(),
+            "swap_ranges: ranges {}..{} and {}..{} overlap",
+            a.start(),
+            a.end(),
+            b.start(),
+            b.end()
+        );
+
+        unsafe {
+            let begin = self.container.begin_mut();
+
+            ptr::swap_nonoverlapping(begin.add(a.start()), begin.add(b.start()), a.len());
+        }
+    }
+
+    /// Rotates the elements within `r` in-place, such that the element at offset `mid`
+    /// from the start of `r` becomes the first element of `r`.
+    ///
+    /// A `mid` larger than the length of `r` is reduced modulo the length of `r`.
+    #[inline]
+    pub fn rotate_range_left(&mut self, r: Range<C, NonEmpty>, mid: usize)
+    where
+        A: GetUncheckedMut,
+    {
+        let mid = mid % r.len();
+
+        // `mid` is less than the length of `r`, so `r.start() + mid` lies within `r`.
+        let (lhs, rhs) = r.split_index(unsafe { Index::new(r.start() + mid) });
+
+        self.reverse(lhs);
+        self.reverse(rhs);
+        self.reverse(r);
+    }
+
+    /// Rotates the elements within `r` in-place, such that every element moves `mid` positions
+    /// towards the end of `r`, wrapping around to the start of `r`.
+    ///
+    /// A `mid` larger than the length of `r` is reduced modulo the length of `r`.
+    #[inline]
+    pub fn rotate_range_right(&mut self, r: Range<C, NonEmpty>, mid: usize)
+    where
+        A: GetUncheckedMut,
+    {
+        let mid = mid % r.len();
+
+        self.rotate_range_left(r, r.len() - mid);
+    }
+
+    /// Copies the elements within `src` to the window of equal length starting at `dest`,
+    /// using a memmove, so the source and destination may overlap.
+    ///
+    /// # Panics
+    /// Panics if the destination window does not fit within the container.
+    #[inline]
+    pub fn copy_within<P>(&mut self, src: Range<C, P>, dest: Index<C>)
+    where
+        T: Copy,
+        A: ContiguousMut,
+    {
+        assert!(
+            src.len() <= self.len() - dest.integer(),
+            "copy_within: destination {}..{} is out of bounds for a container of length {}",
+            dest.integer(),
+            dest.integer() + src.len(),
+            self.len()
+        );
+
+        unsafe {
+            let begin = self.container.begin_mut();
+
+            ptr::copy(begin.add(src.start()), begin.add(dest.integer()), src.len());
+        }
+    }
+
+    /// Returns mutable references to the elements at `a` and `b`, in that order,
+    /// or None if `a` and `b` are the same index.
+    #[inline(always)]
+    pub fn get_pair_mut(&mut self, a: Index<C>, b: Index<C>) -> Option<(&mut T, &mut T)>
+    where
+        A: ContiguousMut,
+    {
+        if a == b {
+            return None;
+        }
+
+        unsafe {
+            let base = self.container.as_mut_slice().as_mut_ptr();
+
+            Some((&mut *base.add(a.integer()), &mut *base.add(b.integer())))
+        }
+    }
+
+    /// Returns mutable references to the elements at each of `indices`, in the same order,
+    /// or None if any two of `indices` are the same index.
+    ///
+    /// The indices are compared pairwise, which is meant for small `N`.
+    #[inline]
+    pub fn get_disjoint_mut<const N: usize>(
+        &mut self,
+        indices: [Index<C>; N],
+    ) -> Option<[&mut T; N]>
+    where
+        A: ContiguousMut,
+    {
+        for (i, index) in indices.iter().enumerate() {
+            if indices[..i].contains(index) {
+                return None;
+            }
+        }
+
+        unsafe {
+            let base = self.container.as_mut_slice().as_mut_ptr();
+
+            Some(indices.map(|index| &mut *base.add(index.integer())))
+        }
+    }
+
+    /// Binary searches the sorted container for `x`.
+    ///
+    /// On success, returns Ok with the index of a matching element.
+    /// If there are multiple matches, any one of them may be returned.
+    /// On failure, returns Err with the index `x` could be inserted at while keeping the container sorted.
+    #[inline]
+    pub fn binary_search(&self, x: &T) -> Result<Index<C>, usize>
+    where
+        T: Ord,
+        A: GetUnchecked,
+    {
+        self.binary_search_by(|item| item.cmp(x))
+    }
+
+    /// Binary searches the sorted container with the comparator function `f`,
+    /// which returns the ordering of an element relative to the target.
+    ///
+    /// See [`Container::binary_search`] for the meaning of the result.
+    #[inline]
+    pub fn binary_search_by<F>(&self, f: F) -> Result<Index<C>, usize>
+    where
+        F: FnMut(&T) -> Ordering,
+        A: GetUnchecked,
+    {
+        self.binary_search_range_by(self.range(), f)
+    }
+
+    /// Binary searches the sorted range `r` with the comparator function `f`,
+    /// which returns the ordering of an element relative to the target.
+    ///
+    /// On success, returns Ok with the index of a matching element.
+    /// On failure, returns Err with the index within the container (not relative to `r`),
+    /// at which the target could be inserted while keeping `r` sorted.
+    /// This index lies within `r.start()..=r.end()`.
+    #[inline]
+    pub fn binary_search_range_by<P, F>(&self, r: Range<C, P>, mut f: F) -> Result<Index<C>, usize>
+    where
+        F: FnMut(&T) -> Ordering,
+        A: GetUnchecked,
+    {
+        let mut range = r.forget_proof();
+
+        while let Some(nonempty) = range.nonempty() {
+            let mid = nonempty.upper_middle();
+            let (lhs, rhs) = nonempty.split_index(mid);
+
+            range = match f(&self[mid]) {
+                Ordering::Less => rhs.tail(),
+                Ordering::Equal => return Ok(mid),
+                Ordering::Greater => lhs,
+            };
+        }
+
+        Err(range.start())
+    }
+
+    /// Binary searches the container, sorted by the key extracted with `f`, for `key`.
+    ///
+    /// See [`Container::binary_search`] for the meaning of the result.
+    #[inline]
+    pub fn binary_search_by_key<K, F>(&self, key: &K, mut f: F) -> Result<Index<C>, usize>
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+        A: GetUnchecked,
+    {
+        self.binary_search_by(|item| f(item).cmp(key))
+    }
+
+    /// Returns the first edge within the sorted range `r` at which `x` could be inserted
+    /// while keeping `r` sorted, that is, the edge before the first element not less than `x`.
+    ///
+    /// The returned edge lies within `r.start()..=r.end()`.
+    #[inline]
+    pub fn lower_bound<P>(&self, r: Range<C, P>, x: &T) -> Index<C, Unknown>
+    where
+        T: Ord,
+        A: GetUnchecked,
+    {
+        self.partition_point(r, |item| item < x)
+    }
+
+    /// Returns the last edge within the sorted range `r` at which `x` could be inserted
+    /// while keeping `r` sorted, that is, the edge before the first element greater than `x`.
+    ///
+    /// The returned edge lies within `r.start()..=r.end()`.
+    #[inline]
+    pub fn upper_bound<P>(&self, r: Range<C, P>, x: &T) -> Index<C, Unknown>
+    where
+        T: Ord,
+        A: GetUnchecked,
+    {
+        self.partition_point(r, |item| item <= x)
+    }
+
+    /// Returns the range of elements within the sorted range `r` that are equal to `x`.
+    ///
+    /// If no element equals `x`, the returned range is empty and
+    /// starts at the edge where `x` could be inserted while keeping `r` sorted.
+    #[inline]
+    pub fn equal_range<P>(&self, r: Range<C, P>, x: &T) -> Range<C>
+    where
+        T: Ord,
+        A: GetUnchecked,
+    {
+        let mut range = r.forget_proof();
+
+        while let Some(nonempty) = range.nonempty() {
+            let mid = nonempty.upper_middle();
+            let (lhs, rhs) = nonempty.split_index(mid);
+
+            match self[mid].cmp(x) {
+                Ordering::Less => range = rhs.tail(),
+                Ordering::Greater => range = lhs,
+                Ordering::Equal => {
+                    // `mid` is a match, so the first match lies in `lhs`,
+                    // and the end of the matches in `rhs`.
+                    let start = self.lower_bound(lhs, x);
+                    let end = self.upper_bound(rhs.tail(), x);
+
+                    return unsafe { Range::from_unknown(start.integer(), end.integer()) };
+                }
+            }
+        }
+
+        range
+    }
+
+    /// Returns the index of the first element for which `pred` returns `true`.
+    #[inline]
+    pub fn position<F>(&self, pred: F) -> Option<Index<C>>
+    where
+        F: FnMut(&T) -> bool,
+        A: Contiguous<Item = T>,
+    {
+        let offset = self.iter().position(pred)?;
+
+        unsafe { Some(Index::new(offset)) }
+    }
+
+    /// Returns the first element for which `pred` returns `true`, together with its index.
+    #[inline]
+    pub fn find<F>(&self, mut pred: F) -> Option<(Index<C>, &T)>
+    where
+        F: FnMut(&T) -> bool,
+        A: Contiguous<Item = T>,
+    {
+        let (offset, item) = self.iter().enumerate().find(|(_, item)| pred(item))?;
+
+        unsafe { Some((Index::new(offset), item)) }
+    }
+
+    /// Returns the index of the last element for which `pred` returns `true`.
+    ///
+    /// The elements are searched in order from the higher indices towards the lower.
+    #[inline]
+    pub fn rposition<F>(&self, pred: F) -> Option<Index<C>>
+    where
+        F: FnMut(&T) -> bool,
+        A: Contiguous<Item = T>,
+    {
+        let offset = self.iter().rev().position(pred)?;
+
+        unsafe { Some(Index::new(self.len() - 1 - offset)) }
+    }
+
+    /// Returns the index of the first element that equals `needle`.
+    #[inline]
+    pub fn index_of(&self, needle: &T) -> Option<Index<C>>
+    where
+        T: PartialEq,
+        A: Contiguous<Item = T>,
+    {
+        let offset = IndexOf::index_of(self.container.as_slice(), needle)?;
+
+        unsafe { Some(Index::new(offset)) }
+    }
+
+    /// Returns the index of the minimum element within `r`, or None if `r` is empty.
+    ///
+    /// If several elements are equally minimum, the index of the first one is returned.
+    #[inline]
+    pub fn min_index<P>(&self, r: Range<C, P>) -> Option<Index<C>>
+    where
+        T: Ord,
+        A: GetUnchecked,
+    {
+        self.min_index_by(r, T::cmp)
+    }
+
+    /// Returns the index of the element within `r` that gives the minimum value
+    /// with respect to the comparison function `cmp`, or None if `r` is empty.
+    ///
+    /// If several elements are equally minimum, the index of the first one is returned.
+    #[inline]
+    pub fn min_index_by<P, F>(&self, r: Range<C, P>, mut cmp: F) -> Option<Index<C>>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUnchecked,
+    {
+        let mut iter = r.into_iter();
+        let mut min = iter.next()?;
+
+        for index in iter {
+            if cmp(&self[index], &self[min]) == Ordering::Less {
+                min = index;
+            }
+        }
+
+        Some(min)
+    }
+
+    /// Returns the index of the element within `r` that gives the minimum value from `f`,
+    /// or None if `r` is empty.
+    ///
+    /// If several elements are equally minimum, the index of the first one is returned.
+    #[inline]
+    pub fn min_index_by_key<P, K, F>(&self, r: Range<C, P>, mut f: F) -> Option<Index<C>>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+        A: GetUnchecked,
+    {
+        self.min_index_by(r, |a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Returns the index of the maximum element within `r`, or None if `r` is empty.
+    ///
+    /// If several elements are equally maximum, the index of the first one is returned.
+    #[inline]
+    pub fn max_index<P>(&self, r: Range<C, P>) -> Option<Index<C>>
+    where
+        T: Ord,
+        A: GetUnchecked,
+    {
+        self.max_index_by(r, T::cmp)
+    }
+
+    /// Returns the index of the element within `r` that gives the maximum value
+    /// with respect to the comparison function `cmp`, or None if `r` is empty.
+    ///
+    /// If several elements are equally maximum, the index of the first one is returned.
+    #[inline]
+    pub fn max_index_by<P, F>(&self, r: Range<C, P>, mut cmp: F) -> Option<Index<C>>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUnchecked,
+    {
+        let mut iter = r.into_iter();
+        let mut max = iter.next()?;
+
+        for index in iter {
+            if cmp(&self[index], &self[max]) == Ordering::Greater {
+                max = index;
+            }
+        }
+
+        Some(max)
+    }
+
+    /// Returns the index of the element within `r` that gives the maximum value from `f`,
+    /// or None if `r` is empty.
+    ///
+    /// If several elements are equally maximum, the index of the first one is returned.
+    #[inline]
+    pub fn max_index_by_key<P, K, F>(&self, r: Range<C, P>, mut f: F) -> Option<Index<C>>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+        A: GetUnchecked,
+    {
+        self.max_index_by(r, |a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Returns `true` if the elements within `r` are sorted.
+    #[inline]
+    pub fn is_sorted<P>(&self, r: Range<C, P>) -> bool
+    where
+        T: PartialOrd,
+        A: GetUnchecked,
+    {
+        self.is_sorted_by(r, |a, b| a <= b)
+    }
+
+    /// Returns `true` if the elements within `r` are sorted according to `f`,
+    /// which returns whether its two arguments are in order.
+    ///
+    /// An empty range, or a range of a single element, is always sorted.
+    #[inline]
+    pub fn is_sorted_by<P, F>(&self, r: Range<C, P>, mut f: F) -> bool
+    where
+        F: FnMut(&T, &T) -> bool,
+        A: GetUnchecked,
+    {
+        r.pairs().all(|(a, b)| f(&self[a], &self[b]))
+    }
+
+    /// Reorders the elements within `r` such that all elements for which `pred` returns `true`
+    /// precede all elements for which `pred` returns `false`.
+    ///
+    /// Returns the range of elements for which `pred` returns `true`,
+    /// which starts at `r.start()`. The order of the elements within each group is unspecified.
+    #[inline]
+    pub fn partition_in_place<P, F>(&mut self, r: Range<C, P>, mut pred: F) -> Range<C>
+    where
+        F: FnMut(&T) -> bool,
+        A: GetUncheckedMut,
+    {
+        let mut iter = r.into_iter();
+        let mut end = r.end();
+
+        while let Some(lo) = iter.find(|&index| !pred(&self[index])) {
+            match iter.rfind(|&index| pred(&self[index])) {
+                Some(hi) => {
+                    self.swap(lo, hi);
+                    end = hi.integer();
+                }
+                None => {
+                    end = lo.integer();
+                    break;
+                }
+            }
+        }
+
+        unsafe { Range::from_unknown(r.start(), end) }
+    }
+
+    /// Moves all elements within `r` for which `keep` returns `true` to the front of `r`,
+    /// preserving their relative order.
+    ///
+    /// Returns the range of kept elements, which starts at `r.start()`.
+    /// The elements after it are the remaining elements of `r`, in an unspecified order.
+    #[inline]
+    pub fn compact<P, F>(&mut self, r: Range<C, P>, mut keep: F) -> Range<C>
+    where
+        F: FnMut(&T) -> bool,
+        A: GetUncheckedMut,
+    {
+        let mut write = r.into_iter();
+        let mut end = r.start();
+
+        for read in r {
+            if keep(&self[read]) {
+                // `write` never overtakes `read`, so it yields an index for every kept element.
+                if let Some(w) = write.next() {
+                    if w.integer() != read.integer() {
+                        self.swap(w, read);
+                    }
+                    end = w.integer() + 1;
+                }
+            }
+        }
+
+        unsafe { Range::from_unknown(r.start(), end) }
+    }
+
+    /// Moves the first element of every run of consecutive equal elements within `r`
+    /// to the front of `r`, preserving their relative order.
+    ///
+    /// Returns the range of unique elements, which starts at `r.start()`.
+    /// The elements after it are the removed duplicates, in an unspecified order.
+    #[inline]
+    pub fn dedup_range<P>(&mut self, r: Range<C, P>) -> Range<C>
+    where
+        T: PartialEq,
+        A: GetUncheckedMut,
+    {
+        self.dedup_range_by(r, |a, b| a == b)
+    }
+
+    /// Moves the first element of every run of consecutive elements within `r`
+    /// for which `same` returns `true` to the front of `r`, preserving their relative order.
+    ///
+    /// `same` is called with the element under consideration and the last unique element.
+    /// Returns the range of unique elements, which starts at `r.start()`.
+    #[inline]
+    pub fn dedup_range_by<P, F>(&mut self, r: Range<C, P>, mut same: F) -> Range<C>
+    where
+        F: FnMut(&T, &T) -> bool,
+        A: GetUncheckedMut,
+    {
+        let r = match r.nonempty() {
+            Some(r) => r,
+            None => return r.forget_proof(),
+        };
+
+        let mut write = r.tail().into_iter();
+        let mut last = r.first();
+
+        for read in r.tail() {
+            if !same(&self[read], &self[last]) {
+                // `write` never overtakes `read`, so it yields an index for every unique element.
+                if let Some(w) = write.next() {
+                    if w.integer() != read.integer() {
+                        self.swap(w, read);
+                    }
+                    last = w;
+                }
+            }
+        }
+
+        unsafe { Range::from_unknown(r.start(), last.integer() + 1) }
+    }
+
+    /// Reorders the elements within `r`, which must not contain `pivot`,
+    /// such that all elements for which `pred(element, pivot)` returns `true` come first.
+    ///
+    /// Returns the range of elements for which `pred` returned `true`.
+    ///
+    /// Every element is swapped with the first element for which `pred` returned `false` so far,
+    /// whatever `pred` returns for it, such that the loop does not branch on `pred`.
+    #[inline]
+    fn partition_against<P, F>(&mut self, r: Range<C, P>, pivot: Index<C>, pred: &mut F) -> Range<C>
+    where
+        F: FnMut(&T, &T) -> bool,
+        A: GetUncheckedMut,
+    {
+        let r = match r.nonempty() {
+            Some(r) => r,
+            None => return r.forget_proof(),
+        };
+
+        let mut end = r.start();
+
+        for current in r {
+            // `end` starts at `r.start()` and grows by at most one per element,
+            // so it never passes `current`.
+            paranoid_assert!(end <= current.integer());
+            let first_false = unsafe { Index::new(end) };
+            let keep = pred(&self[current], &self[pivot]);
+
+            self.swap(first_false, current);
+            end += keep as usize;
+        }
+
+        unsafe { Range::from_unknown(r.start(), end) }
+    }
+
+    /// Returns the index of the median of the first, the middle and the last element of `r`.
+    #[inline]
+    pub(crate) fn median_of_three<F>(&self, r: Range<C, NonEmpty>, cmp: &mut F) -> Index<C>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUnchecked,
+    {
+        self.median_of(r.first(), r.upper_middle(), r.last(), cmp)
+    }
+
+    /// Returns the index of the pivot for partitioning `r`: the median of three
+    /// if `r` holds less than `PSEUDO_MEDIAN_THRESHOLD` elements,
+    /// and a recursive pseudo-median of elements spread over `r` otherwise.
+    #[inline]
+    fn choose_pivot<F>(&self, r: Range<C, NonEmpty>, cmp: &mut F) -> Index<C>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUnchecked,
+    {
+        if r.len() < PSEUDO_MEDIAN_THRESHOLD {
+            return self.median_of_three(r, cmp);
+        }
+
+        let eighth = r.len() / 8;
+        let start = r.start();
+
+        self.pseudo_median(start, start + eighth * 4, start + eighth * 7, eighth, cmp)
+    }
+
+    /// Returns the index of the median of the elements at `a`, `b` and `c`,
+    /// or of the pseudo-medians of the `n` elements starting at each, if `n` is large enough.
+    ///
+    /// Every index this looks at lies within `a..a + n`, `b..b + n` or `c..c + n`.
+    fn pseudo_median<F>(&self, a: usize, b: usize, c: usize, n: usize, cmp: &mut F) -> Index<C>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUnchecked,
+    {
+        let (a, b, c) = if n >= PSEUDO_MEDIAN_THRESHOLD / 8 {
+            let eighth = n / 8;
+            let mut median = |at: usize| {
+                self.pseudo_median(at, at + eighth * 4, at + eighth * 7, eighth, cmp)
+                    .integer()
+            };
+
+            (median(a), median(b), median(c))
+        } else {
+            (a, b, c)
+        };
+
+        paranoid_assert!(a < self.len() && b < self.len() && c < self.len());
+
+        // `choose_pivot` only passes indices within `r`, along with the length of their eighth.
+        unsafe { self.median_of(Index::new(a), Index::new(b), Index::new(c), cmp) }
+    }
+
+    /// Returns the index of the median of the elements at `a`, `b` and `c`.
+    #[inline]
+    fn median_of<F>(&self, a: Index<C>, b: Index<C>, c: Index<C>, cmp: &mut F) -> Index<C>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUnchecked,
+    {
+        let mut less = |x: Index<C>, y: Index<C>| cmp(&self[x], &self[y]) == Ordering::Less;
+
+        // If `a` is less than both or neither of `b` and `c`, the median is one of those two.
+        let (ab, ac) = (less(a, b), less(a, c));
+
+        if ab == ac {
+            if less(b, c) == ab {
+                b
+            } else {
+                c
+            }
+        } else {
+            a
+        }
+    }
+
+    /// Reorders the elements within `r` into the elements less than the pivot,
+    /// the elements equal to the pivot, and the elements greater than the pivot,
+    /// using the median of three as the pivot.
+    ///
+    /// Returns the three groups as ranges, the middle one is never empty.
+    #[inline]
+    fn partition_around_pivot<F>(
+        &mut self,
+        r: Range<C, NonEmpty>,
+        cmp: &mut F,
+    ) -> (Range<C>, Range<C>, Range<C>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUncheckedMut,
+    {
+        let pivot = self.median_of_three(r, cmp);
+        self.swap(r.first(), pivot);
+
+        let pivot = r.first();
+        let less = self.partition_against(r.tail(), pivot, &mut |x, p| cmp(x, p) == Ordering::Less);
+
+        // Move the pivot in between the lesser and the remaining elements.
+        let pivot = unsafe { Index::new(less.end() - 1) };
+        self.swap(r.first(), pivot);
+
+        let rest = unsafe { Range::<C>::from_unknown(pivot.integer() + 1, r.end()) };
+        let equal = self.partition_against(rest, pivot, &mut |x, p| cmp(x, p) == Ordering::Equal);
+
+        unsafe {
+            (
+                Range::from_unknown(r.start(), pivot.integer()),
+                Range::from_unknown(pivot.integer(), equal.end()),
+                Range::from_unknown(equal.end(), r.end()),
+            )
+        }
+    }
+
+    /// Reorders the elements within `r` such that the element at `r.start() + n`
+    /// is at its final sorted position.
+    ///
+    /// Returns the range of elements before it, which are all less than or equal to it,
+    /// its index, and the range of elements after it, which are all greater than or equal to it.
+    ///
+    /// # Panics
+    /// Panics if `n` is not less than the length of `r`.
+    #[inline]
+    pub fn select_nth_unstable(
+        &mut self,
+        r: Range<C, NonEmpty>,
+        n: usize,
+    ) -> (Range<C>, Index<C>, Range<C>)
+    where
+        T: Ord,
+        A: GetUncheckedMut,
+    {
+        self.select_nth_unstable_by(r, n, T::cmp)
+    }
+
+    /// Reorders the elements within `r` such that the element at `r.start() + n`
+    /// is at its final position, when sorted with the comparison function `cmp`.
+    ///
+    /// Returns the range of elements before it, its index, and the range of elements after it.
+    ///
+    /// # Panics
+    /// Panics if `n` is not less than the length of `r`.
+    #[inline]
+    pub fn select_nth_unstable_by<F>(
+        &mut self,
+        r: Range<C, NonEmpty>,
+        n: usize,
+        mut cmp: F,
+    ) -> (Range<C>, Index<C>, Range<C>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUncheckedMut,
+    {
+        assert!(
+            n < r.len(),
+            "select_nth_unstable: index {} is out of bounds for a range of length {}",
+            n,
+            r.len()
+        );
+
+        let target = r.start() + n;
+        let mut range = r;
+
+        loop {
+            let (less, equal, greater) = self.partition_around_pivot(range, &mut cmp);
+
+            let next = if target < equal.start() {
+                less
+            } else if target >= equal.end() {
+                greater
+            } else {
+                return r.split_around(unsafe { Index::new(target) });
+            };
+
+            // `next` contains `target`, so it is never empty.
+            range = unsafe { next.assume_nonempty() };
+        }
+    }
+
+    /// Swaps the elements at `a` and `b` if the element at `b` is less than the element at `a`.
+    ///
+    /// Both elements are written either way, such that this does not branch on `cmp`.
+    #[inline(always)]
+    pub(crate) fn compare_swap<F>(&mut self, a: Index<C>, b: Index<C>, cmp: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUncheckedMut,
+    {
+        let swap = cmp(&self[b], &self[a]) == Ordering::Less;
+
+        // As in `swap`, both elements are read before either is written.
+        unsafe {
+            let value_a = ptr::read(&self[a]);
+            let value_b = ptr::read(&self[b]);
+            let (lower, upper) = if swap {
+                (value_b, value_a)
+            } else {
+                (value_a, value_b)
+            };
+
+            ptr::write(&mut self[a], lower);
+            ptr::write(&mut self[b], upper);
+        }
+    }
+
+    /// Sorts the elements within `r` with a sorting network of 19 comparators.
+    #[inline]
+    pub(crate) fn sort_network_8<F>(&mut self, r: Range<C, ExactLen<8>>, cmp: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUncheckedMut,
+    {
+        const NETWORK: [(usize, usize); 19] = [
+            (0, 2),
+            (1, 3),
+            (4, 6),
+            (5, 7),
+            (0, 4),
+            (1, 5),
+            (2, 6),
+            (3, 7),
+            (0, 1),
+            (2, 3),
+            (4, 5),
+            (6, 7),
+            (2, 4),
+            (3, 5),
+            (1, 4),
+            (3, 6),
+            (1, 2),
+            (3, 4),
+            (5, 6),
+        ];
+
+        for &(a, b) in NETWORK.iter() {
+            // The length of `r` is proven to be exactly 8, and every comparator lies within `0..8`.
+            let (a, b) = unsafe { (Index::new(r.start() + a), Index::new(r.start() + b)) };
+
+            self.compare_swap(a, b, cmp);
+        }
+    }
+
+    /// Sorts the elements within `r`, which holds at most `SMALL_SORT_THRESHOLD` elements.
+    ///
+    /// Both halves of `r` are sorted with a sorting network for their first 8 elements,
+    /// and insertion sort for the rest, after which they are merged.
+    #[inline]
+    fn small_sort<F>(&mut self, r: Range<C, NonEmpty>, cmp: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUncheckedMut,
+    {
+        let (lhs, rhs) = r.split_in_half();
+
+        if lhs.len() < 8 {
+            return self.presorted_insertion_sort(r.forget_proof(), cmp);
+        }
+
+        self.presorted_insertion_sort(lhs, cmp);
+        self.presorted_insertion_sort(rhs.forget_proof(), cmp);
+        self.merge_halves(r, cmp);
+    }
+
+    /// Sorts the elements within `r` with insertion sort,
+    /// after sorting its first 8 elements with a sorting network.
+    #[inline(always)]
+    fn presorted_insertion_sort<F>(&mut self, r: Range<C>, cmp: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUncheckedMut,
+    {
+        let prefix = r
+            .nonempty()
+            .and_then(|r| r.contains(r.start() + 8))
+            .map_or(r, |ninth| r.split_index(ninth).0);
+
+        match prefix.with_exact_size::<8>() {
+            Some(prefix) => {
+                self.sort_network_8(prefix, cmp);
+                self.insertion_sort_tail(r, 8, cmp);
+            }
+            None => self.insertion_sort_range(r, cmp),
+        }
+    }
+
+    /// Merges the sorted halves of `r`, as split by `Range::split_in_half`,
+    /// where `r` holds at most `SMALL_SORT_THRESHOLD` elements.
+    ///
+    /// The merge runs from the front and the back at once, into a buffer on the stack,
+    /// without branching on `cmp`. The elements are only moved back into `r`
+    /// if both directions met, which does not hold for a `cmp` that is not a total order.
+    #[inline]
+    fn merge_halves<F>(&mut self, r: Range<C, NonEmpty>, cmp: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUncheckedMut,
+    {
+        let mut buffer: [MaybeUninit<T>; SMALL_SORT_THRESHOLD] =
+            unsafe { MaybeUninit::uninit().assume_init() };
+        let buffer = &mut buffer[..r.len()];
+        let len = buffer.len();
+
+        // Each cursor moves at most once per step, and there are `len / 2` steps in either
+        // direction, so even if `cmp` is not a total order and the cursors run past each other,
+        // no cursor leaves `r` before it is read, as the first half holds `len / 2` elements.
+        let mid = r.start() + len / 2;
+        let vet = |at: usize| {
+            paranoid_assert!(r.start() <= at && at < r.end());
+            unsafe { Index::<C>::new(at) }
+        };
+
+        let (mut front_lhs, mut front_rhs) = (r.start(), mid);
+        let (mut back_lhs, mut back_rhs) = (mid.wrapping_sub(1), r.end() - 1);
+
+        for k in 0..len / 2 {
+            let (lhs, rhs) = (vet(front_lhs), vet(front_rhs));
+            let take_lhs = cmp(&self[rhs], &self[lhs]) != Ordering::Less;
+
+            buffer[k] =
+                MaybeUninit::new(unsafe { ptr::read(&self[if take_lhs { lhs } else { rhs }]) });
+            front_lhs += take_lhs as usize;
+            front_rhs += !take_lhs as usize;
+
+            let (lhs, rhs) = (vet(back_lhs), vet(back_rhs));
+            let take_lhs = cmp(&self[rhs], &self[lhs]) == Ordering::Less;
+
+            buffer[len - 1 - k] =
+                MaybeUninit::new(unsafe { ptr::read(&self[if take_lhs { lhs } else { rhs }]) });
+            back_lhs = back_lhs.wrapping_sub(take_lhs as usize);
+            back_rhs = back_rhs.wrapping_sub(!take_lhs as usize);
+        }
+
+        if len % 2 == 1 {
+            let take_lhs = front_lhs != back_lhs.wrapping_add(1);
+            let last = vet(if take_lhs { front_lhs } else { front_rhs });
+
+            buffer[len / 2] = MaybeUninit::new(unsafe { ptr::read(&self[last]) });
+            front_lhs += take_lhs as usize;
+            front_rhs += !take_lhs as usize;
+        }
+
+        // Every element was read exactly once if both directions met,
+        // otherwise `r` is left as it was, holding both sorted halves.
+        if front_lhs == back_lhs.wrapping_add(1) && front_rhs == back_rhs.wrapping_add(1) {
+            for (index, value) in r.into_iter().zip(buffer.iter()) {
+                unsafe { ptr::write(&mut self[index], value.as_ptr().read()) };
+            }
+        }
+    }
+
+    /// Sorts the elements within `r` with insertion sort, using the comparator function `cmp`.
+    #[inline]
+    pub(crate) fn insertion_sort_range<P, F>(&mut self, r: Range<C, P>, cmp: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUncheckedMut,
+    {
+        self.insertion_sort_tail(r, 1, cmp)
+    }
+
+    /// Sorts the elements within `r` with insertion sort,
+    /// where the first `presorted` elements of `r` are sorted already.
+    ///
+    /// Every element is moved out into a hole that is shifted down,
+    /// and written back by an `InsertionHole` should `cmp` panic.
+    #[inline]
+    fn insertion_sort_tail<P, F>(&mut self, r: Range<C, P>, presorted: usize, cmp: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUncheckedMut,
+    {
+        for index in r.into_iter().skip(presorted) {
+            let mut prevs = r.first().up_to(index).rev();
+
+            // `index` is never the first element of `r`, so there is an element before it.
+            let prev = match prevs.next() {
+                Some(prev) => prev,
+                None => continue,
+            };
+
+            if cmp(&self[index], &self[prev]) != Ordering::Less {
+                continue;
+            }
+
+            let value = ManuallyDrop::new(unsafe { ptr::read(&self[index]) });
+            let mut hole = InsertionHole {
+                src: &*value,
+                dest: &mut self[prev],
+            };
+
+            unsafe { ptr::write(&mut self[index], ptr::read(&self[prev])) };
+
+            for prev in prevs {
+                if cmp(&value, &self[prev]) != Ordering::Less {
+                    break;
+                }
+
+                let next = hole.dest;
+                unsafe { ptr::write(next, ptr::read(&self[prev])) };
+                hole.dest = &mut self[prev];
+            }
+
+            // Dropping `hole` moves `value` into it.
+        }
+    }
+
+    /// Restores the max-heap property of `heap`, by moving the element at `node` down.
+    #[inline]
+    pub(crate) fn sift_down<F>(&mut self, heap: Range<C, NonEmpty>, mut node: Index<C>, cmp: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUncheckedMut,
+    {
+        // The left child lies `node - heap.start() + 1` past `node`, which may exceed `usize::MAX`.
+        while let Some(mut child) = node
+            .integer()
+            .checked_add(node.integer() - heap.start() + 1)
+            .and_then(|left| heap.contains(left))
+        {
+            if let Some(right) = heap.contains(child.integer() + 1) {
+                if cmp(&self[child], &self[right]) == Ordering::Less {
+                    child = right;
+                }
+            }
+
+            if cmp(&self[node], &self[child]) != Ordering::Less {
+                break;
+            }
+
+            self.swap(node, child);
+            node = child;
+        }
+    }
+
+    /// Sorts the elements within `r` with heapsort, using the comparator function `cmp`.
+    #[inline]
+    pub(crate) fn heapsort<F>(&mut self, r: Range<C, NonEmpty>, cmp: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUncheckedMut,
+    {
+        // The second half of `r` are leaves, which are heaps already.
+        for node in r.into_iter().take(r.len() / 2).rev() {
+            self.sift_down(r, node, cmp);
+        }
+
+        let mut heap = r;
+
+        loop {
+            self.swap(heap.first(), heap.last());
+
+            match heap.head().nonempty() {
+                Some(rest) => heap = rest,
+                None => break,
+            }
+
+            self.sift_down(heap, heap.first(), cmp);
+        }
+    }
+
+    /// Sorts the elements within `r`.
+    ///
+    /// This sort is unstable, does not allocate, and is *O*(*n* \* log(*n*)) worst-case.
+    #[inline]
+    pub fn sort_range_unstable<P>(&mut self, r: Range<C, P>)
+    where
+        T: Ord,
+        A: GetUncheckedMut,
+    {
+        self.sort_range_unstable_by(r, T::cmp)
+    }
+
+    /// Sorts the elements within `r` with the comparator function `cmp`.
+    ///
+    /// This sort is unstable, does not allocate, and is *O*(*n* \* log(*n*)) worst-case.
+    /// It is an introsort: a quicksort with a branchless partition around a pseudo-median pivot,
+    /// that groups the elements equal to the pivot, switches to sorting networks and
+    /// insertion sort for short ranges, and falls back to heapsort when partitioning
+    /// keeps turning out unbalanced.
+    #[inline]
+    pub fn sort_range_unstable_by<P, F>(&mut self, r: Range<C, P>, mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUncheckedMut,
+    {
+        let limit = (usize::BITS - r.len().leading_zeros()) as usize * 2;
+
+        self.introsort(r.forget_proof(), None, &mut cmp, limit);
+    }
+
+    /// Sorts the elements within `range`, falling back to heapsort once `limit` runs out.
+    ///
+    /// `ancestor` is the pivot directly before `range`, if any, which no element is less than.
+    /// If the pivot of `range` compares equal to it, the elements equal to the pivot are
+    /// skipped in one pass, which keeps ranges with many equal elements from degrading.
+    #[inline]
+    fn introsort<F>(
+        &mut self,
+        mut range: Range<C>,
+        mut ancestor: Option<Index<C>>,
+        cmp: &mut F,
+        mut limit: usize,
+    ) where
+        F: FnMut(&T, &T) -> Ordering,
+        A: GetUncheckedMut,
+    {
+        while let Some(nonempty) = range.nonempty() {
+            if nonempty.len() <= SMALL_SORT_THRESHOLD {
+                self.small_sort(nonempty, cmp);
+                return;
+            }
+
+            if limit == 0 {
+                self.heapsort(nonempty, cmp);
+                return;
+            }
+
+            limit -= 1;
+
+            let median = self.choose_pivot(nonempty, cmp);
+            let pivot = nonempty.first();
+
+            if median != pivot {
+                self.swap(pivot, median);
+            }
+
+            if let Some(ancestor) = ancestor {
+                if cmp(&self[ancestor], &self[pivot]) != Ordering::Less {
+                    let equal = self.partition_against(nonempty.tail(), pivot, &mut |x, p| {
+                        cmp(p, x) != Ordering::Less
+                    });
+
+                    range = unsafe { Range::from_unknown(equal.end(), nonempty.end()) };
+                    continue;
+                }
+            }
+
+            let less = self.partition_against(nonempty.tail(), pivot, &mut |x, p| {
+                cmp(x, p) == Ordering::Less
+            });
+
+            // Move the pivot in between the lesser and the remaining elements.
+            let mid = unsafe { Index::new(less.end() - 1) };
+
+            if mid != pivot {
+                self.swap(pivot, mid);
+            }
+
+            let (less, mid, greater) = nonempty.split_around(mid);
+
+            // Recurse into the shorter side, and loop on the longer one.
+            if less.len() < greater.len() {
+                self.introsort(less, ancestor, cmp, limit);
+                range = greater;
+                ancestor = Some(mid);
+            } else {
+                self.introsort(greater, Some(mid), cmp, limit);
+                range = less;
+            }
+        }
+    }
+
+    /// Calls `f` for every chunk of `size` elements of the container, in order.
+    /// The last chunk is shorter if `size` does not divide the length of the container.
+    ///
+    /// Every chunk is a container with a contract of its own,
+    /// so indices of one chunk can not be used with another.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    #[inline]
+    pub fn for_each_chunk<F>(&self, size: usize, mut f: F)
+    where
+        F: for<'id> FnMut(Container<Signed<'id>, &[T]>),
+        A: Contiguous,
+    {
+        for chunk in self.container.as_slice().chunks(size) {
+            f(Container::new(chunk));
+        }
+    }
+
+    /// Calls `f` for every mutable chunk of `size` elements of the container, in order.
+    /// The last chunk is shorter if `size` does not divide the length of the container.
+    ///
+    /// Every chunk is a container with a contract of its own,
+    /// so indices of one chunk can not be used with another.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    #[inline]
+    pub fn for_each_chunk_mut<F>(&mut self, size: usize, mut f: F)
+    where
+        F: for<'id> FnMut(Container<Signed<'id>, &mut [T]>),
+        A: ContiguousMut,
+    {
+        for chunk in self.container.as_mut_slice().chunks_mut(size) {
+            f(Container::new(chunk));
+        }
+    }
+
+    /// Calls `f` with the elements within `r` as a container with a contract of its own,
+    /// so indices of this container can not be used with it, nor the other way around.
+    #[inline]
+    pub fn subcontainer<P, F, Out>(&self, r: Range<C, P>, f: F) -> Out
+    where
+        F: for<'id> FnOnce(Container<Signed<'id>, &[T]>) -> Out,
+        A: Contiguous,
+    {
+        f(Container::new(&self[r]))
+    }
+
+    /// Calls `f` with the elements within `r` as a mutable container with a contract of its own,
+    /// so indices of this container can not be used with it, nor the other way around.
+    #[inline]
+    pub fn subcontainer_mut<P, F, Out>(&mut self, r: Range<C, P>, f: F) -> Out
+    where
+        F: for<'id> FnOnce(Container<Signed<'id>, &mut [T]>) -> Out,
+        A: ContiguousMut,
+    {
+        f(Container::new(&mut self[r]))
+    }
+
+    /// Returns the range of the container that `sub` refers to,
+    /// or None if `sub` does not lie within the container.
+    ///
+    /// For zero-sized types the position of `sub` can not be recovered,
+    /// nor told apart from any other slice, so None is always returned.
+    #[inline]
+    pub fn range_of(&self, sub: &[T]) -> Option<Range<C>>
+    where
+        A: Contiguous<Item = T>,
+    {
+        let size = core::mem::size_of::<T>();
+
+        if size == 0 {
+            return None;
+        }
+
+        let begin = self.container.begin() as usize;
+        let end = self.container.end() as usize;
+        let start = sub.as_ptr() as usize;
+
+        if start < begin || start > end || !(start - begin).is_multiple_of(size) {
+            return None;
+        }
+
+        let offset = (start - begin) / size;
+
+        self.vet_range(offset, offset + sub.len()).ok()
+    }
+
+    /// Returns an iterator over the maximal runs of adjacent elements for which `eq` returns `true`.
+    ///
+    /// The runs are yielded in order, and together cover the whole container.
+    #[inline]
+    pub fn group_by<F>(&self, eq: F) -> GroupBy<'_, C, A, F>
+    where
+        F: FnMut(&T, &T) -> bool,
+        A: GetUnchecked,
+    {
+        GroupBy::new(self, eq)
+    }
+
+    /// Returns an iterator over the ranges in between the elements for which `is_sep` returns `true`.
+    ///
+    /// Like [`slice::split`], leading, trailing and consecutive separators
+    /// yield empty ranges, and an empty container yields a single empty range.
+    #[inline]
+    pub fn split_by<F>(&self, is_sep: F) -> SplitBy<'_, C, A, F>
+    where
+        F: FnMut(&T) -> bool,
+        A: GetUnchecked,
+    {
+        SplitBy::new(self, is_sep)
+    }
+
+    /// Returns an iterator over the non-empty ranges in between the elements for which `is_sep` returns `true`.
+    #[inline]
+    pub fn split_by_nonempty<F>(&self, is_sep: F) -> SplitByNonEmpty<'_, C, A, F>
+    where
+        F: FnMut(&T) -> bool,
+        A: GetUnchecked,
+    {
+        self.split_by(is_sep).filter_map(|r| r.nonempty())
+    }
+
+    /// Returns an iterator over the indices of the elements within `r` for which `pred` returns `true`,
+    /// in order.
+    #[inline]
+    pub fn indices_where<P, F>(&self, r: Range<C, P>, pred: F) -> IndicesWhere<'_, C, A, F>
+    where
+        F: FnMut(&T) -> bool,
+        A: GetUnchecked,
+    {
+        IndicesWhere::new(self, r, pred)
+    }
+
+    /// Returns an iterator over all overlapping windows of length `size`, as ranges.
+    ///
+    /// Like [`slice::windows`], nothing is yielded if `size` is larger than the length of the container.
+    #[inline]
+    pub fn windows(&self, size: NonZeroUsize) -> Windows<C> {
+        Windows::new(self.len(), size)
+    }
+
+    /// Returns a lending iterator over all overlapping windows of length `size`,
+    /// each lent out as a container with a contract of its own.
+    ///
+    /// Like [`windows`](Container::windows), nothing is yielded if `size` is larger than the length of the container,
+    /// and the last window ends at the end of the container.
+    #[inline]
+    pub fn windows_branded(&self, size: NonZeroUsize) -> BrandedWindows<'_, T>
+    where
+        A: Contiguous,
+    {
+        BrandedWindows::new(self.container.as_slice(), size)
+    }
+
+    /// Returns an iterator over consecutive, non-overlapping chunks of length `size`, as ranges,
+    /// starting at the front of the container.
+    ///
+    /// Like [`slice::chunks`], the last chunk is shorter if `size` does not divide the length of the container.
+    #[inline]
+    pub fn chunks(&self, size: NonZeroUsize) -> Chunks<C> {
+        Chunks::new(self.len(), size)
+    }
+
+    /// Returns an iterator over consecutive, non-overlapping chunks of length `size`, as ranges,
+    /// starting at the back of the container.
+    ///
+    /// Like [`slice::rchunks`], the last chunk, which is the one at the front of the container,
+    /// is shorter if `size` does not divide the length of the container.
+    #[inline]
+    pub fn rchunks(&self, size: NonZeroUsize) -> RChunks<C> {
+        RChunks::new(self.len(), size)
+    }
+
+    /// Returns `true` if `needle` is a prefix of the container.
+    #[inline]
+    pub fn starts_with(&self, needle: &[T]) -> bool
+    where
+        T: PartialEq,
+        A: Contiguous<Item = T>,
+    {
+        self.as_slice().starts_with(needle)
+    }
+
+    /// Returns `true` if `needle` is a suffix of the container.
+    #[inline]
+    pub fn ends_with(&self, needle: &[T]) -> bool
+    where
+        T: PartialEq,
+        A: Contiguous<Item = T>,
+    {
+        self.as_slice().ends_with(needle)
+    }
+
+    /// Returns `true` if `needle` is a prefix of the elements within `r`.
+    ///
+    /// Returns `false` if `needle` is longer than `r`.
+    #[inline]
+    pub fn range_starts_with<P>(&self, r: Range<C, P>, needle: &[T]) -> bool
+    where
+        T: PartialEq,
+        A: Contiguous<Item = T>,
+    {
+        self[r].starts_with(needle)
+    }
+
+    /// Returns `true` if `needle` is a suffix of the elements within `r`.
+    ///
+    /// Returns `false` if `needle` is longer than `r`.
+    #[inline]
+    pub fn range_ends_with<P>(&self, r: Range<C, P>, needle: &[T]) -> bool
+    where
+        T: PartialEq,
+        A: Contiguous<Item = T>,
+    {
+        self[r].ends_with(needle)
+    }
+
+    /// Returns `true` if any element within `r` equals `x`.
+    #[inline]
+    pub fn contains_value<P>(&self, r: Range<C, P>, x: &T) -> bool
+    where
+        T: PartialEq,
+        A: Contiguous<Item = T>,
+    {
+        IndexOf::index_of(&self[r], x).is_some()
+    }
+
+    /// Returns `true` if the ranges `a` and `b` are of equal length, and hold equal elements.
+    /// The ranges may overlap.
+    ///
+    /// For bytes, and other types that can be compared bytewise, this is a single `memcmp`.
+    #[inline]
+    pub fn eq_ranges<P, Q>(&self, a: Range<C, P>, b: Range<C, Q>) -> bool
+    where
+        T: PartialEq,
+        A: Contiguous<Item = T>,
+    {
+        self[a] == self[b]
+    }
+
+    /// Folds every element within `r` into an accumulator by applying `f`, in order.
+    #[inline]
+    pub fn fold<P, B, F>(&self, r: Range<C, P>, init: B, f: F) -> B
+    where
+        F: FnMut(B, &T) -> B,
+        A: Contiguous,
+    {
+        self[r].iter().fold(init, f)
+    }
+
+    /// Folds every element within `r` into an accumulator by applying `f`, in order,
+    /// stopping at the first error `f` returns.
+    #[inline]
+    pub fn try_fold<P, B, E, F>(&self, r: Range<C, P>, init: B, f: F) -> Result<B, E>
+    where
+        F: FnMut(B, &T) -> Result<B, E>,
+        A: Contiguous,
+    {
+        self[r].iter().try_fold(init, f)
+    }
+
+    /// Returns the number of elements within `r` for which `pred` returns `true`.
+    ///
+    /// See [`count_byte`](Container::count_byte) for a faster way of counting equal bytes.
+    #[inline]
+    pub fn count<P, F>(&self, r: Range<C, P>, mut pred: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+        A: Contiguous,
+    {
+        self[r].iter().filter(|x| pred(x)).count()
+    }
+
+    /// Copies the elements within `r` into `out`.
+    ///
+    /// Returns an error if `out` differs in length from `r`, in which case nothing is copied.
+    #[inline]
+    pub fn copy_to_slice<P>(&self, r: Range<C, P>, out: &mut [T]) -> Result<(), LengthMismatch>
+    where
+        T: Copy,
+        A: Contiguous,
+    {
+        if r.len() != out.len() {
+            return Err(LengthMismatch::new(r.len(), out.len()));
+        }
+
+        out.copy_from_slice(&self[r]);
+
+        Ok(())
+    }
+
+    /// Clones the elements within `r` into `out`.
+    ///
+    /// Returns an error if `out` differs in length from `r`, in which case nothing is cloned.
+    #[inline]
+    pub fn clone_to_slice<P>(&self, r: Range<C, P>, out: &mut [T]) -> Result<(), LengthMismatch>
+    where
+        T: Clone,
+        A: Contiguous,
+    {
+        if r.len() != out.len() {
+            return Err(LengthMismatch::new(r.len(), out.len()));
+        }
+
+        out.clone_from_slice(&self[r]);
+
+        Ok(())
+    }
+
+    /// Copies the elements within `r` into the uninitialized `out`,
+    /// and returns `out` as an initialized slice.
+    ///
+    /// Returns an error if `out` differs in length from `r`, in which case nothing is copied.
+    #[inline]
+    pub fn copy_to_uninit<'o, P>(
+        &self,
+        r: Range<C, P>,
+        out: &'o mut [MaybeUninit<T>],
+    ) -> Result<&'o mut [T], LengthMismatch>
+    where
+        T: Copy,
+        A: Contiguous,
+    {
+        if r.len() != out.len() {
+            return Err(LengthMismatch::new(r.len(), out.len()));
+        }
+
+        unsafe {
+            ptr::copy_nonoverlapping(self[r].as_ptr(), out.as_mut_ptr() as *mut T, out.len());
+
+            Ok(&mut *(out as *mut [MaybeUninit<T>] as *mut [T]))
+        }
+    }
+}
+
+impl<C: for<'s> Contract<'s>, A, T> Container<C, A>
+where
+    A: SplitUnchecked<Item = T>,
+{
+    #[inline(always)]
+    pub fn split_first(&self) -> Option<(&T, &<A as SplitUnchecked>::Split)>
+    where
+        <A as SplitUnchecked>::Split: GetUnchecked<Item = T>,
+    {
+        // The bound on <A as SplitUnchecked>::Split allows the `unchecked(0)` call.
+        unsafe {
+            if !self.is_empty() {
+                let split = self.split_at(Index::<C>::new(1));
+
+                let (lhs, rhs) = split;
+
+                Some((lhs.unchecked(0), rhs))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Returns the last element, and a container over all elements before it.
+    /// Returns None if the container is empty.
+    #[inline(always)]
+    pub fn split_last(&self) -> Option<(&T, &<A as SplitUnchecked>::Split)>
+    where
+        <A as SplitUnchecked>::Split: GetUnchecked<Item = T>,
+    {
+        // The bound on <A as SplitUnchecked>::Split allows the `unchecked(0)` call.
+        unsafe {
+            if !self.is_empty() {
+                let split = self.split_at(Index::<C>::new(self.len() - 1));
+
+                let (lhs, rhs) = split;
+
+                Some((rhs.unchecked(0), lhs))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Divides one container into two at `index`.
+    /// The first will contain all indices from `[0, index)` and the second will contain all indices from
+    /// [mid, len).
+    ///
+    /// `index` may also be an [`Edge`], in which case splitting at the start or the end
+    /// returns one empty container.
+    #[inline(always)]
+    pub fn split_at<P>(
+        &self,
+        index: Index<C, P>,
+    ) -> (&<A as SplitUnchecked>::Split, &<A as SplitUnchecked>::Split) {
+        unsafe { self.container.split_unchecked(index.integer()) }
+    }
+
+    /// Divides one container into two at `index`,
+    /// and calls `f` with both halves as containers with a contract of their own,
+    /// together with the range of the second half,
+    /// and a [`Translator`] between indices of this container and indices of the halves.
+    /// Proof `P` of the index transfers to the range of the second half.
+    ///
+    /// ```
+    /// use signed::region;
+    ///
+    /// region(&[1, 2, 3, 4][..], |s| {
+    ///     let mid = s.vet(2).unwrap();
+    ///
+    ///     s.split_at_with(mid, |lhs, rhs, range, _| {
+    ///         assert_eq!(lhs.len(), 2);
+    ///         assert_eq!(rhs[range.last()], 4);
+    ///     });
+    /// });
+    /// ```
+    ///
+    /// Both halves get a contract of their own,
+    /// so an index of one half can not be used with the other:
+    ///
+    /// ```compile_fail
+    /// use signed::region;
+    ///
+    /// region(&[1, 2, 3, 4][..], |s| {
+    ///     let mid = s.vet(2).unwrap();
+    ///
+    ///     s.split_at_with(mid, |lhs, rhs, range, _| {
+    ///         // The left half only holds 2 elements.
+    ///         let _ = lhs[range.last()];
+    ///     });
+    /// });
+    /// ```
+    #[inline(always)]
+    pub fn split_at_with<'a, P, F, Out>(&'a self, index: Index<C, P>, f: F) -> Out
+    where
+        P: Transferable,
+        <A as SplitUnchecked>::Split: ContainerTrait,
+        F: for<'l, 'r> FnOnce(
+            Container<Signed<'l>, &'a <A as SplitUnchecked>::Split>,
+            Container<Signed<'r>, &'a <A as SplitUnchecked>::Split>,
+            Range<Signed<'r>, P>,
+            Translator<C, Signed<'l>, Signed<'r>>,
+        ) -> Out,
+    {
+        let (lhs, rhs) = self.split_at(index);
+        let range = unsafe { Range::from_any(0, rhs.base_len()) };
+
+        let translator = unsafe { Translator::new(index.integer()) };
+
+        f(Container::new(lhs), Container::new(rhs), range, translator)
+    }
+
+    /// Like [`split_at_with`](Container::split_at_with),
+    /// but hands each half over together with its full range.
+    ///
+    /// The range of the first half is None if `index` is at the start of the container,
+    /// the range of the second half carries proof `P` of the index.
+    #[inline(always)]
+    pub fn split_at_with_ranges<'a, P, F, Out>(&'a self, index: Index<C, P>, f: F) -> Out
+    where
+        P: Transferable,
+        <A as SplitUnchecked>::Split: ContainerTrait,
+        F: for<'l, 'r> FnOnce(
+            (
+                Container<Signed<'l>, &'a <A as SplitUnchecked>::Split>,
+                Option<Range<Signed<'l>, NonEmpty>>,
+            ),
+            (
+                Container<Signed<'r>, &'a <A as SplitUnchecked>::Split>,
+                Range<Signed<'r>, P>,
+            ),
+            Translator<C, Signed<'l>, Signed<'r>>,
+        ) -> Out,
+    {
+        self.split_at_with(index, |lhs, rhs, range, translator| {
+            let left = lhs.range().nonempty();
+
+            f((lhs, left), (rhs, range), translator)
+        })
+    }
+}
+
+impl<C: for<'s> Contract<'s>, A, T> Container<C, A>
+where
+    A: SplitUncheckedMut<Item = T>,
+{
+    #[inline(always)]
+    pub fn split_first_mut(&mut self) -> Option<(&mut T, &mut <A as SplitUnchecked>::Split)>
+    where
+        <A as SplitUnchecked>::Split: GetUncheckedMut<Item = T>,
+    {
+        // The bound on <A as SplitUnchecked>::Split allows the `unchecked_mut(0)` call.
+        unsafe {
+            if !self.is_empty() {
+                let split = self.split_at_mut(Index::<C>::new(1));
+
+                let (lhs, rhs) = split;
+
+                Some((lhs.unchecked_mut(0), rhs))
+            } else {
+                None
+            }
+        }
+    }
+    /// Returns the last element, and a container over all elements before it, both mutable.
+    /// Returns None if the container is empty.
+    #[inline(always)]
+    pub fn split_last_mut(&mut self) -> Option<(&mut T, &mut <A as SplitUnchecked>::Split)>
+    where
+        <A as SplitUnchecked>::Split: GetUncheckedMut<Item = T>,
+    {
+        // The bound on <A as SplitUnchecked>::Split allows the `unchecked_mut(0)` call.
+        unsafe {
+            if !self.is_empty() {
+                let split = self.split_at_mut(Index::<C>::new(self.len() - 1));
+
+                let (lhs, rhs) = split;
+
+                Some((rhs.unchecked_mut(0), lhs))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Divides one mutable container into two at `index`.
+    /// The first will contain all indices from `[0, index)` and the second will contain all indices from
+    /// [mid, len).
+    ///
+    /// `index` may also be an [`Edge`], in which case splitting at the start or the end
+    /// returns one empty container.
+    #[inline(always)]
+    pub fn split_at_mut<P>(
+        &mut self,
+        index: Index<C, P>,
+    ) -> (
+        &mut <A as SplitUnchecked>::Split,
+        &mut <A as SplitUnchecked>::Split,
+    ) {
+        unsafe { self.container.split_unchecked_mut(index.integer()) }
+    }
+
+    /// Divides one mutable container into two at `index`,
+    /// and calls `f` with both halves as containers with a contract of their own,
+    /// together with the range of the second half,
+    /// and a [`Translator`] between indices of this container and indices of the halves.
+    /// Proof `P` of the index transfers to the range of the second half.
+    ///
+    /// Both halves get a contract of their own,
+    /// so an index of one half can not be used with the other:
+    ///
+    /// ```compile_fail
+    /// use signed::region;
+    ///
+    /// let mut v = vec![1, 2, 3, 4];
+    ///
+    /// region(v.as_mut_slice(), |mut s| {
+    ///     let mid = s.vet(2).unwrap();
+    ///
+    ///     s.split_at_mut_with(mid, |mut lhs, rhs, range, _| {
+    ///         // The left half only holds 2 elements.
+    ///         lhs[range.last()] = 0;
+    ///     });
+    /// });
+    /// ```
+    #[inline(always)]
+    pub fn split_at_mut_with<'a, P, F, Out>(&'a mut self, index: Index<C, P>, f: F) -> Out
+    where
+        P: Transferable,
+        <A as SplitUnchecked>::Split: ContainerTrait,
+        F: for<'l, 'r> FnOnce(
+            Container<Signed<'l>, &'a mut <A as SplitUnchecked>::Split>,
+            Container<Signed<'r>, &'a mut <A as SplitUnchecked>::Split>,
+            Range<Signed<'r>, P>,
+            Translator<C, Signed<'l>, Signed<'r>>,
+        ) -> Out,
+    {
+        let (lhs, rhs) = self.split_at_mut(index);
+        let range = unsafe { Range::from_any(0, rhs.base_len()) };
+
+        let translator = unsafe { Translator::new(index.integer()) };
+
+        f(Container::new(lhs), Container::new(rhs), range, translator)
+    }
+
+    /// Like [`split_at_mut_with`](Container::split_at_mut_with),
+    /// but hands each half over together with its full range.
+    ///
+    /// The range of the first half is None if `index` is at the start of the container,
+    /// the range of the second half carries proof `P` of the index.
+    #[inline(always)]
+    pub fn split_at_mut_with_ranges<'a, P, F, Out>(&'a mut self, index: Index<C, P>, f: F) -> Out
+    where
+        P: Transferable,
+        <A as SplitUnchecked>::Split: ContainerTrait,
+        F: for<'l, 'r> FnOnce(
+            (
+                Container<Signed<'l>, &'a mut <A as SplitUnchecked>::Split>,
+                Option<Range<Signed<'l>, NonEmpty>>,
+            ),
+            (
+                Container<Signed<'r>, &'a mut <A as SplitUnchecked>::Split>,
+                Range<Signed<'r>, P>,
+            ),
+            Translator<C, Signed<'l>, Signed<'r>>,
+        ) -> Out,
+    {
+        self.split_at_mut_with(index, |lhs, rhs, range, translator| {
+            let left = lhs.range().nonempty();
+
+            f((lhs, left), (rhs, range), translator)
+        })
+    }
+}
+
+/// Swaps the element at index `i` of `left` with the element at index `j` of `right`.
+///
+/// Both containers are borrowed exclusively, so they can not overlap.
+#[inline(always)]
+pub fn swap_between<CL, CR, AL, AR, T>(
+    left: &mut Container<CL, AL>,
+    i: Index<CL>,
+    right: &mut Container<CR, AR>,
+    j: Index<CR>,
+) where
+    CL: for<'s> Contract<'s>,
+    CR: for<'s> Contract<'s>,
+    AL: GetUncheckedMut<Item = T>,
+    AR: GetUncheckedMut<Item = T>,
+{
+    core::mem::swap(&mut left[i], &mut right[j]);
+}
+
+/// Swaps the elements within range `lr` of `left` with the elements within range `rr` of `right`.
+///
+/// Returns an error if the two ranges differ in length, in which case no element is swapped.
+#[inline]
+pub fn swap_ranges_between<CL, CR, AL, AR, T, P, Q>(
+    left: &mut Container<CL, AL>,
+    lr: Range<CL, P>,
+    right: &mut Container<CR, AR>,
+    rr: Range<CR, Q>,
+) -> Result<(), LengthMismatch>
+where
+    CL: for<'s> Contract<'s>,
+    CR: for<'s> Contract<'s>,
+    AL: ContiguousMut<Item = T>,
+    AR: ContiguousMut<Item = T>,
+{
+    if lr.len() != rr.len() {
+        return Err(LengthMismatch::new(lr.len(), rr.len()));
+    }
+
+    unsafe {
+        core::ptr::swap_nonoverlapping(
+            left.container.begin_mut().add(lr.start()),
+            right.container.begin_mut().add(rr.start()),
+            lr.len(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Searches a slice for an element, with a fast path for bytes.
+pub(crate) trait IndexOf: PartialEq + Sized {
+    fn index_of(haystack: &[Self], needle: &Self) -> Option<usize>;
+}
+
+impl<T: PartialEq> IndexOf for T {
+    #[inline]
+    default fn index_of(haystack: &[Self], needle: &Self) -> Option<usize> {
+        haystack.iter().position(|item| item == needle)
+    }
+}
+
+impl IndexOf for u8 {
+    #[inline]
+    fn index_of(haystack: &[Self], needle: &Self) -> Option<usize> {
+        super::memchr::memchr(*needle, haystack)
+    }
+}
+
+impl IndexOf for i8 {
+    #[inline]
+    fn index_of(haystack: &[Self], needle: &Self) -> Option<usize> {
+        let haystack =
+            unsafe { core::slice::from_raw_parts(haystack.as_ptr() as *const u8, haystack.len()) };
+
+        super::memchr::memchr(*needle as u8, haystack)
+    }
+}
+
+use core::ops;
+
+impl<C: for<'s> Contract<'s>, A, T> Container<C, A>
+where
+    A: ContiguousMut<Item = T>,
+{
+    /// Copies all elements from `src` into `self`, which are proven to be of equal length.
+    ///
+    /// Containers of a different brand can not be mixed up without a proof:
+    ///
+    /// ```compile_fail
+    /// use signed::region;
+    ///
+    /// let (mut a, mut b, mut c) = (vec![0; 3], vec![1; 3], vec![2; 4]);
+    ///
+    /// region(a.as_mut_slice(), |mut a| {
+    ///     region(b.as_mut_slice(), |b| {
+    ///         region(c.as_mut_slice(), |c| {
+    ///             let proof = a.len_eq(&b).unwrap();
+    ///             a.copy_from(&c, proof);
+    ///         })
+    ///     })
+    /// });
+    /// ```
+    #[inline(always)]
+    pub fn copy_from<D, B>(&mut self, src: &Container<D, B>, _proof: LenEq<C, D>)
+    where
+        T: Copy,
+        D: for<'s> Contract<'s>,
+        B: Contiguous<Item = T>,
+    {
+        unsafe {
+            ptr::copy_nonoverlapping(
+                src.container.begin(),
+                self.container.begin_mut(),
+                self.len(),
+            );
+        }
+    }
+
+    /// Copies the elements within `src_range` of `src` into `dst_range` of `self`.
+    ///
+    /// Returns an error if the two ranges differ in length, in which case nothing is copied.
+    #[inline]
+    pub fn copy_range_from<D, B, P, Q>(
+        &mut self,
+        dst_range: Range<C, P>,
+        src: &Container<D, B>,
+        src_range: Range<D, Q>,
+    ) -> Result<(), LengthMismatch>
+    where
+        T: Copy,
+        D: for<'s> Contract<'s>,
+        B: Contiguous<Item = T>,
+    {
+        if dst_range.len() != src_range.len() {
+            return Err(LengthMismatch::new(dst_range.len(), src_range.len()));
+        }
+
+        unsafe {
+            ptr::copy_nonoverlapping(
+                src.container.begin().add(src_range.start()),
+                self.container.begin_mut().add(dst_range.start()),
+                dst_range.len(),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Copies the elements within `r` `k` positions towards the end of the container,
+    /// as a single `memmove`. The elements of `r` not overwritten keep their value.
+    ///
+    /// Returns the range the elements were copied to,
+    /// or None if it would not fit within the container, in which case nothing is copied.
+    #[inline]
+    pub fn shift_right(&mut self, r: Range<C, NonEmpty>, k: usize) -> Option<Range<C, NonEmpty>>
+    where
+        T: Copy,
+        A: ContiguousMut,
+    {
+        let end = r.end().checked_add(k)?;
+        let dst = self.vet_range(end - r.len(), end).ok()?;
+
+        unsafe { Some(self.shift_unchecked(r, dst.start())) }
+    }
+
+    /// Copies the elements within `r` `k` positions towards the start of the container,
+    /// as a single `memmove`. The elements of `r` not overwritten keep their value.
+    ///
+    /// Returns the range the elements were copied to,
+    /// or None if it would not fit within the container, in which case nothing is copied.
+    #[inline]
+    pub fn shift_left(&mut self, r: Range<C, NonEmpty>, k: usize) -> Option<Range<C, NonEmpty>>
+    where
+        T: Copy,
+        A: ContiguousMut,
+    {
+        let dst = self
+            .vet_range(r.start().checked_sub(k)?, r.end() - k)
+            .ok()?;
+
+        unsafe { Some(self.shift_unchecked(r, dst.start())) }
+    }
+
+    /// Copies the elements within `r` to the range of the same length starting at `to`.
+    ///
+    /// # Unsafe
+    /// `to + r.len()` must not exceed the length of the container.
+    #[inline(always)]
+    unsafe fn shift_unchecked(&mut self, r: Range<C, NonEmpty>, to: usize) -> Range<C, NonEmpty>
+    where
+        T: Copy,
+        A: ContiguousMut,
+    {
+        let begin = self.as_mut_ptr();
+        ptr::copy(begin.add(r.start()), begin.add(to), r.len());
+
+        Range::from_nonempty(to, to + r.len())
+    }
+
+    /// Clones the elements within `r` `k` positions towards the end of the container,
+    /// like [`shift_right`](Container::shift_right) does for `Copy` types.
+    #[inline]
+    pub fn shift_right_cloned(
+        &mut self,
+        r: Range<C, NonEmpty>,
+        k: usize,
+    ) -> Option<Range<C, NonEmpty>>
+    where
+        T: Clone,
+        A: GetUncheckedMut,
+    {
+        let end = r.end().checked_add(k)?;
+        let dst = self.vet_range(end - r.len(), end).ok()?.nonempty()?;
+
+        // Walking back to front never clones an element that was already overwritten.
+        for (from, to) in r.into_iter().rev().zip(dst.into_iter().rev()) {
+            let value = self[from].clone();
+            self[to] = value;
+        }
+
+        Some(dst)
+    }
+
+    /// Clones the elements within `r` `k` positions towards the start of the container,
+    /// like [`shift_left`](Container::shift_left) does for `Copy` types.
+    #[inline]
+    pub fn shift_left_cloned(
+        &mut self,
+        r: Range<C, NonEmpty>,
+        k: usize,
+    ) -> Option<Range<C, NonEmpty>>
+    where
+        T: Clone,
+        A: GetUncheckedMut,
+    {
+        let dst = self
+            .vet_range(r.start().checked_sub(k)?, r.end() - k)
+            .ok()?
+            .nonempty()?;
+
+        // Walking front to back never clones an element that was already overwritten.
+        for (from, to) in r.into_iter().zip(dst) {
+            let value = self[from].clone();
+            self[to] = value;
+        }
+
+        Some(dst)
+    }
+
+    /// Calls `f` with every element of `self` and the element of `other` at the same position.
+    ///
+    /// The `proof` guarantees both containers are of equal length,
+    /// so no element is checked against the bounds.
+    #[inline]
+    pub fn zip_with<D, B, U, F>(&mut self, other: &Container<D, B>, _proof: LenEq<C, D>, mut f: F)
+    where
+        D: for<'s> Contract<'s>,
+        A: GetUncheckedMut,
+        B: GetUnchecked<Item = U>,
+        F: FnMut(&mut T, &U),
+    {
+        for i in 0..self.len() {
+            unsafe {
+                f(
+                    self.container.unchecked_mut(i),
+                    other.container.unchecked(i),
+                )
+            }
+        }
+    }
+
+    /// Calls `f` with every element within `dst_range` of `self`
+    /// and the element of `src_range` of `other` at the same offset.
+    ///
+    /// Returns an error if the two ranges differ in length, in which case `f` is never called.
+    #[inline]
+    pub fn zip_range_with<D, B, U, P, Q, F>(
+        &mut self,
+        dst_range: Range<C, P>,
+        other: &Container<D, B>,
+        src_range: Range<D, Q>,
+        mut f: F,
+    ) -> Result<(), LengthMismatch>
+    where
+        D: for<'s> Contract<'s>,
+        A: GetUncheckedMut,
+        B: GetUnchecked<Item = U>,
+        F: FnMut(&mut T, &U),
+    {
+        if dst_range.len() != src_range.len() {
+            return Err(LengthMismatch::new(dst_range.len(), src_range.len()));
+        }
+
+        for (d, s) in (dst_range.start()..dst_range.end()).zip(src_range.start()..) {
+            unsafe {
+                f(
+                    self.container.unchecked_mut(d),
+                    other.container.unchecked(s),
+                )
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Calls `f` on every element within `r`, in order.
+    #[inline]
+    pub fn apply<P, F>(&mut self, r: Range<C, P>, f: F)
+    where
+        F: FnMut(&mut T),
+        A: ContiguousMut,
+    {
+        self[r].iter_mut().for_each(f)
+    }
+
+    /// Calls `f` on every element within `r` together with its index, in order.
+    #[inline]
+    pub fn apply_indexed<P, F>(&mut self, r: Range<C, P>, mut f: F)
+    where
+        F: FnMut(Index<C>, &mut T),
+        A: ContiguousMut,
+    {
+        for (index, element) in r.into_iter().zip(self[r].iter_mut()) {
+            f(index, element);
+        }
+    }
+
+    /// Fills the range `r` with `value`,
+    /// cloning it for every element except the last one, which `value` is moved into.
+    ///
+    /// Filling an empty range does not clone `value`.
+    #[inline]
+    pub fn fill<P>(&mut self, r: Range<C, P>, value: T)
+    where
+        T: Clone,
+    {
+        if let Some((last, elements)) = self[r].split_last_mut() {
+            for element in elements {
+                element.clone_from(&value);
+            }
+
+            *last = value;
+        }
+    }
+}
+
+impl<C: for<'s> Contract<'s>, A> Container<C, A>
+where
+    A: Contiguous<Item = u8>,
+{
+    /// Returns the index of the first byte within `r` that equals `needle`.
+    #[inline]
+    pub fn find_byte<P>(&self, r: Range<C, P>, needle: u8) -> Option<Index<C>> {
+        let offset = super::memchr::memchr(needle, &self[r])?;
+
+        unsafe { Some(Index::new(r.start() + offset)) }
+    }
+
+    /// Returns the index of the last byte within `r` that equals `needle`.
+    #[inline]
+    pub fn find_byte_rev<P>(&self, r: Range<C, P>, needle: u8) -> Option<Index<C>> {
+        let offset = super::memchr::memrchr(needle, &self[r])?;
+
+        unsafe { Some(Index::new(r.start() + offset)) }
+    }
+
+    /// Returns the number of bytes within `r` that equal `needle`.
+    #[inline]
+    pub fn count_byte<P>(&self, r: Range<C, P>, needle: u8) -> usize {
+        super::memchr::count(needle, &self[r])
+    }
+}
+
+// &self[i]
+impl<C: for<'s> Contract<'s>, A> ops::Index<Index<C>> for Container<C, A>
+where
+    A: GetUnchecked,
+{
+    type Output = A::Item;
+
+    #[inline(always)]
+    fn index(&self, index: Index<C>) -> &Self::Output {
+        paranoid_assert!(
+            index.integer() < self.len(),
+            "index {} is out of bounds of a container of length {}",
+            index.integer(),
+            self.len()
+        );
+
+        unsafe { self.container.unchecked(index.integer()) }
+    }
+}
+
+// &mut self[i]
+impl<C: for<'s> Contract<'s>, A> ops::IndexMut<Index<C>> for Container<C, A>
+where
+    A: GetUncheckedMut,
+{
+    #[inline(always)]
+    fn index_mut(&mut self, index: Index<C>) -> &mut Self::Output {
+        paranoid_assert!(
+            index.integer() < self.len(),
+            "index {} is out of bounds of a container of length {}",
+            index.integer(),
+            self.len()
+        );
+
+        unsafe { self.container.unchecked_mut(index.integer()) }
+    }
+}
+
+// &self[usize], checked
+impl<C: for<'s> Contract<'s>, A> ops::Index<usize> for Container<C, A>
+where
+    A: GetUnchecked,
+{
+    type Output = A::Item;
+
+    #[inline(always)]
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.vet(index) {
+            Ok(index) => &self[index],
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+// &mut self[usize], checked
+impl<C: for<'s> Contract<'s>, A> ops::IndexMut<usize> for Container<C, A>
+where
+    A: GetUncheckedMut,
+{
+    #[inline(always)]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match self.vet(index) {
+            Ok(index) => &mut self[index],
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+// &self[r]
+impl<C: for<'s> Contract<'s>, A, T, P> ops::Index<Range<C, P>> for Container<C, A>
+where
+    A: Contiguous<Item = T>,
+{
+    type Output = [T];
+
+    #[inline(always)]
+    fn index(&self, r: Range<C, P>) -> &Self::Output {
+        paranoid_assert!(
+            r.end() <= self.len(),
+            "edge {} is out of bounds of a container of length {}",
+            r.end(),
+            self.len()
+        );
+
+        unsafe { self.container.as_slice().get_unchecked(r.start()..r.end()) }
+    }
+}
+
+// &mut self[r]
+impl<C: for<'s> Contract<'s>, A, P> ops::IndexMut<Range<C, P>> for Container<C, A>
+where
+    A: ContiguousMut,
+{
+    #[inline(always)]
+    fn index_mut(&mut self, r: Range<C, P>) -> &mut Self::Output {
+        paranoid_assert!(
+            r.end() <= self.len(),
+            "edge {} is out of bounds of a container of length {}",
+            r.end(),
+            self.len()
+        );
+
+        unsafe {
+            self.container
+                .as_mut_slice()
+                .get_unchecked_mut(r.start()..r.end())
+        }
+    }
+}
+
+// &self[i..]
+impl<C: for<'s> Contract<'s>, A, T, P> ops::Index<ops::RangeFrom<Index<C, P>>> for Container<C, A>
+where
+    A: Contiguous<Item = T>,
+{
+    type Output = [T];
+
+    #[inline(always)]
+    fn index(&self, r: ops::RangeFrom<Index<C, P>>) -> &Self::Output {
+        let i = r.start.integer();
+
+        paranoid_assert!(
+            i <= self.len(),
+            "edge {} is out of bounds of a container of length {}",
+            i,
+            self.len()
+        );
+
+        unsafe { self.container.as_slice().get_unchecked(i..) }
+    }
+}
+
+// &mut self[i..]
+impl<C: for<'s> Contract<'s>, A, P> ops::IndexMut<ops::RangeFrom<Index<C, P>>> for Container<C, A>
+where
+    A: ContiguousMut,
+{
+    #[inline(always)]
+    fn index_mut(&mut self, r: ops::RangeFrom<Index<C, P>>) -> &mut Self::Output {
+        let i = r.start.integer();
+
+        paranoid_assert!(
+            i <= self.len(),
+            "edge {} is out of bounds of a container of length {}",
+            i,
+            self.len()
+        );
+
+        unsafe { self.container.as_mut_slice().get_unchecked_mut(i..) }
+    }
+}
+
+// &self[..i]
+impl<C: for<'s> Contract<'s>, A, T, P> ops::Index<ops::RangeTo<Index<C, P>>> for Container<C, A>
+where
+    A: Contiguous<Item = T>,
+{
+    type Output = [T];
+
+    #[inline(always)]
+    fn index(&self, r: ops::RangeTo<Index<C, P>>) -> &Self::Output {
+        let i = r.end.integer();
+
+        paranoid_assert!(
+            i <= self.len(),
+            "edge {} is out of bounds of a container of length {}",
+            i,
+            self.len()
+        );
+
+        unsafe { self.container.as_slice().get_unchecked(..i) }
+    }
+}
+
+// &mut self[..i]
+impl<C: for<'s> Contract<'s>, A, P> ops::IndexMut<ops::RangeTo<Index<C, P>>> for Container<C, A>
+where
+    A: ContiguousMut,
+{
+    #[inline(always)]
+    fn index_mut(&mut self, r: ops::RangeTo<Index<C, P>>) -> &mut Self::Output {
+        let i = r.end.integer();
+
+        paranoid_assert!(
+            i <= self.len(),
+            "edge {} is out of bounds of a container of length {}",
+            i,
+            self.len()
+        );
+
+        unsafe { self.container.as_mut_slice().get_unchecked_mut(..i) }
+    }
+}
+
+// &self[..]
+impl<C: for<'s> Contract<'s>, A, T> ops::Index<ops::RangeFull> for Container<C, A>
+where
+    A: Contiguous<Item = T>,
+{
+    type Output = [T];
+
+    #[inline(always)]
+    fn index(&self, _: ops::RangeFull) -> &Self::Output {
+        self.container.as_slice()
+    }
+}
+
+// &mut self[..]
+impl<C: for<'s> Contract<'s>, A> ops::IndexMut<ops::RangeFull> for Container<C, A>
+where
+    A: ContiguousMut,
 {
     fn index_mut(&mut self, _: ops::RangeFull) -> &mut Self::Output {
         self.container.as_mut_slice()
     }
 }
+
+impl<'a, C: for<'s> Contract<'s>, A, T: 'a> IntoIterator for &'a Container<C, A>
+where
+    A: Contiguous<Item = T>,
+{
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, C: for<'s> Contract<'s>, A, T: 'a> IntoIterator for &'a mut Container<C, A>
+where
+    A: ContiguousMut<Item = T>,
+{
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// &self[start..end], checked
+impl<C: for<'s> Contract<'s>, A, T> ops::Index<ops::Range<usize>> for Container<C, A>
+where
+    A: Contiguous<Item = T>,
+{
+    type Output = [T];
+
+    #[inline(always)]
+    fn index(&self, r: ops::Range<usize>) -> &Self::Output {
+        match self.vet_range(r.start, r.end) {
+            Ok(r) => &self[r],
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+// &mut self[start..end], checked
+impl<C: for<'s> Contract<'s>, A> ops::IndexMut<ops::Range<usize>> for Container<C, A>
+where
+    A: ContiguousMut,
+{
+    #[inline(always)]
+    fn index_mut(&mut self, r: ops::Range<usize>) -> &mut Self::Output {
+        match self.vet_range(r.start, r.end) {
+            Ok(r) => &mut self[r],
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{swap_between, swap_ranges_between, SMALL_SORT_THRESHOLD};
+    use crate::{region, test_util::xorshift};
+    use core::num::NonZeroUsize;
+
+    #[test]
+    fn is_partitioned() {
+        let mut v = vec![1, 3, 5, 2, 4];
+
+        region(v.as_mut_slice(), |s| {
+            let odd = |x: &i32| x % 2 == 1;
+
+            assert!(s.is_partitioned(s.range(), odd));
+
+            let boundary = s.partition_boundary(s.range(), odd).unwrap();
+            assert_eq!(boundary.integer(), 3);
+            assert_eq!(s[boundary], 2);
+        });
+    }
+
+    #[test]
+    fn is_not_partitioned() {
+        let mut v = vec![1, 2, 3, 4];
+
+        region(v.as_mut_slice(), |s| {
+            let odd = |x: &i32| x % 2 == 1;
+
+            assert!(!s.is_partitioned(s.range(), odd));
+            assert!(s.partition_boundary(s.range(), odd).is_none());
+        });
+    }
+
+    #[test]
+    fn is_partitioned_empty() {
+        let mut v: Vec<i32> = vec![];
+
+        region(v.as_mut_slice(), |s| {
+            assert!(s.is_partitioned(s.range(), |_| unreachable!()));
+            assert!(s
+                .partition_boundary(s.range(), |_| unreachable!())
+                .is_none());
+        });
+    }
+
+    #[test]
+    fn is_partitioned_all_true_all_false() {
+        let mut v = vec![2, 4, 6];
+
+        region(v.as_mut_slice(), |s| {
+            assert!(s.is_partitioned(s.range(), |x| x % 2 == 0));
+            assert!(s.partition_boundary(s.range(), |x| x % 2 == 0).is_none());
+
+            assert!(s.is_partitioned(s.range(), |x| x % 2 == 1));
+            let boundary = s.partition_boundary(s.range(), |x| x % 2 == 1).unwrap();
+            assert_eq!(boundary.integer(), 0);
+        });
+    }
+
+    #[test]
+    fn partition3_all_equal() {
+        let mut v = vec![5, 5, 5, 5];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range().nonempty().unwrap();
+            let (lt, eq, gt) = s.partition3(r, |x| x.cmp(&5));
+
+            assert!(lt.is_empty());
+            assert!(eq == r);
+            assert!(gt.is_empty());
+        });
+    }
+
+    #[test]
+    fn partition3_no_equal() {
+        let mut v = vec![9, 1, 8, 2, 7, 3];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range().nonempty().unwrap();
+            let (lt, eq, gt) = s.partition3(r, |x| x.cmp(&5));
+
+            assert!(eq.is_empty());
+            assert_eq!(lt.len(), 3);
+            assert_eq!(gt.len(), 3);
+            assert!(s[lt].iter().all(|x| *x < 5));
+            assert!(s[gt].iter().all(|x| *x > 5));
+        });
+    }
+
+    #[test]
+    fn partition3_tiles_range() {
+        let mut v = vec![0, 3, 1, 2, 3, 2, 1, 3, 2, 9];
+
+        region(v.as_mut_slice(), |mut s| {
+            let (_, r) = s.split_at_index(s.range().nonempty().unwrap().first().after());
+            let r = r.nonempty().unwrap();
+            let (lt, eq, gt) = s.partition3(r.head().nonempty().unwrap(), |x| x.cmp(&2));
+
+            assert_eq!(lt.start(), 1);
+            assert_eq!(lt.end(), eq.start());
+            assert_eq!(eq.end(), gt.start());
+            assert_eq!(gt.end(), 9);
+
+            assert_eq!(s[lt], [1, 1]);
+            assert_eq!(s[eq], [2, 2, 2]);
+            assert_eq!(s[gt], [3, 3, 3]);
+            assert_eq!(s[..lt.first()], [0]);
+            assert_eq!(s[r.last()], 9);
+
+            let mut sorted = s[..].to_vec();
+            sorted.sort();
+            assert_eq!(sorted, [0, 1, 1, 2, 2, 2, 3, 3, 3, 9]);
+        });
+    }
+
+    #[test]
+    fn binary_search_nearest() {
+        let mut v = vec![10, 20, 30, 40];
+
+        region(v.as_mut_slice(), |s| {
+            let r = s.range().nonempty().unwrap();
+            let nearest = |x| s.binary_search_nearest(r, &x).integer();
+
+            // Below the first, and above the last element.
+            assert_eq!(nearest(0), 0);
+            assert_eq!(nearest(100), 3);
+
+            // Exactly between two elements ties towards the lower index.
+            assert_eq!(nearest(25), 1);
+            assert_eq!(nearest(26), 2);
+            assert_eq!(nearest(24), 1);
+
+            // Exact hits.
+            for (i, x) in [10, 20, 30, 40].iter().enumerate() {
+                assert_eq!(nearest(*x), i);
+            }
+        });
+    }
+
+    #[test]
+    fn binary_search_nearest_extremes() {
+        let mut v = vec![i32::MIN, i32::MAX];
+
+        region(v.as_mut_slice(), |s| {
+            let r = s.range().nonempty().unwrap();
+            let nearest = |x| s.binary_search_nearest(r, &x).integer();
+
+            // The distances from 0 to both elements exceed `i32::MAX`.
+            assert_eq!(nearest(0), 1);
+            assert_eq!(nearest(-1), 0);
+            assert_eq!(nearest(i32::MIN), 0);
+            assert_eq!(nearest(i32::MAX), 1);
+        });
+    }
+
+    #[test]
+    fn binary_search_nearest_by_key() {
+        let mut v = vec![(1, 'a'), (4, 'b'), (9, 'c')];
+
+        region(v.as_mut_slice(), |s| {
+            let r = s.range().nonempty().unwrap();
+            let nearest = |x: i32| {
+                let i = s.binary_search_nearest_by_key(r, &x, |e| e.0, |a, b| (a - b).abs());
+                s[i].1
+            };
+
+            assert_eq!(nearest(-5), 'a');
+            assert_eq!(nearest(6), 'b');
+            assert_eq!(nearest(7), 'c');
+            assert_eq!(nearest(9), 'c');
+            assert_eq!(nearest(50), 'c');
+        });
+    }
+
+    #[test]
+    fn swap_between_split_halves() {
+        let mut v = vec![1, 2, 3, 4, 5, 6];
+
+        region(v.as_mut_slice(), |mut s| {
+            let mid = s.range().nonempty().unwrap().upper_middle();
+            let (lhs, rhs) = s.split_at_mut(mid);
+
+            region(lhs, |mut lhs| {
+                region(rhs, |mut rhs| {
+                    let i = lhs.range().nonempty().unwrap().first();
+                    let j = rhs.range().nonempty().unwrap().last();
+
+                    swap_between(&mut lhs, i, &mut rhs, j);
+                })
+            });
+
+            assert_eq!(s[..], [6, 2, 3, 4, 5, 1]);
+        });
+    }
+
+    #[test]
+    fn swap_ranges_between_split_halves() {
+        let mut v = vec![1, 2, 3, 4, 5, 6, 7];
+
+        region(v.as_mut_slice(), |mut s| {
+            let mid = s.range().nonempty().unwrap().upper_middle();
+            let (lhs, rhs) = s.split_at_mut(mid);
+
+            region(lhs, |mut lhs| {
+                region(rhs, |mut rhs| {
+                    let lr = lhs.range();
+                    let second = rhs.range().nonempty().unwrap().first().after();
+                    let (_, rr) = rhs.split_at_index(second);
+
+                    assert_eq!(swap_ranges_between(&mut lhs, lr, &mut rhs, rr), Ok(()));
+                })
+            });
+
+            assert_eq!(s[..], [5, 6, 7, 4, 1, 2, 3]);
+        });
+    }
+
+    #[test]
+    fn swap_ranges_between_length_mismatch() {
+        let mut a = vec![1, 2, 3];
+        let mut b = vec![4, 5];
+
+        region(a.as_mut_slice(), |mut a| {
+            region(b.as_mut_slice(), |mut b| {
+                let (ar, br) = (a.range(), b.range());
+                let err = swap_ranges_between(&mut a, ar, &mut b, br).unwrap_err();
+
+                assert_eq!((err.left(), err.right()), (3, 2));
+                assert_eq!(b[..], [4, 5]);
+            });
+
+            assert_eq!(a[..], [1, 2, 3]);
+        });
+    }
+
+    #[test]
+    fn atomic_histogram() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::thread;
+
+        let input: Vec<usize> = (0..1000).map(|i| (i * 7 + 3) % 10).collect();
+        let buckets: Vec<AtomicUsize> = (0..10).map(|_| AtomicUsize::new(0)).collect();
+
+        region(buckets.as_slice(), |s| {
+            // Vet every bucket index once, and share them across all threads.
+            let indices: Vec<_> = s.range().into_iter().collect();
+
+            thread::scope(|scope| {
+                for chunk in input.chunks(100) {
+                    let (s, indices) = (&s, &indices);
+
+                    scope.spawn(move || {
+                        for value in chunk {
+                            s[indices[*value]].fetch_add(1, Ordering::Relaxed);
+                        }
+                    });
+                }
+            });
+        });
+
+        let mut expected = [0; 10];
+        for value in &input {
+            expected[*value] += 1;
+        }
+
+        let counted: Vec<usize> = buckets.iter().map(|b| b.load(Ordering::Relaxed)).collect();
+        assert_eq!(counted, expected);
+    }
+
+    #[test]
+    fn interpolation_search_agrees_with_binary_search() {
+        let mut seed = 0x9e37_79b9_7f4a_7c15;
+
+        for len in 1..64 {
+            let spread = 1 + xorshift(&mut seed) % 1000;
+            let mut v: Vec<u64> = (0..len).map(|_| xorshift(&mut seed) % spread).collect();
+            v.sort_unstable();
+
+            region(v.as_mut_slice(), |s| {
+                let r = s.range().nonempty().unwrap();
+
+                for x in 0..spread + 2 {
+                    match (s.interpolation_search(r, &x), s[..].binary_search(&x)) {
+                        (Ok(found), Ok(_)) => assert_eq!(s[found], x),
+                        (Err(a), Err(b)) => assert_eq!(a, b),
+                        (found, expected) => panic!(
+                            "searching {} in {:?}: {:?} vs {:?}",
+                            x,
+                            &s[..],
+                            found.map(|i| i.integer()),
+                            expected
+                        ),
+                    }
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn interpolation_search_degenerate() {
+        let mut equal = vec![7u32; 100];
+
+        region(equal.as_mut_slice(), |s| {
+            let r = s.range().nonempty().unwrap();
+
+            assert_eq!(s[s.interpolation_search(r, &7).unwrap()], 7);
+            assert_eq!(s.interpolation_search(r, &6).err(), Some(0));
+            assert_eq!(s.interpolation_search(r, &8).err(), Some(100));
+        });
+
+        let mut single = vec![3i64];
+
+        region(single.as_mut_slice(), |s| {
+            let r = s.range().nonempty().unwrap();
+
+            assert_eq!(s.interpolation_search(r, &3).unwrap().integer(), 0);
+            assert_eq!(s.interpolation_search(r, &i64::MIN).err(), Some(0));
+            assert_eq!(s.interpolation_search(r, &i64::MAX).err(), Some(1));
+        });
+
+        let mut floats = vec![0.5, 1.0, 1.5, 2.0];
+
+        region(floats.as_mut_slice(), |s| {
+            let r = s.range().nonempty().unwrap();
+
+            assert_eq!(s.interpolation_search(r, &1.5).unwrap().integer(), 2);
+            assert_eq!(s.interpolation_search(r, &1.25).err(), Some(2));
+            assert!(s.interpolation_search(r, &f64::NAN).is_err());
+        });
+    }
+
+    #[test]
+    fn next_permutation_enumerates_all() {
+        let mut v = vec![9, 0, 1, 2, 3, 9];
+
+        region(v.as_mut_slice(), |mut s| {
+            let second = s.range().nonempty().unwrap().first().after();
+            let (_, tail) = s.split_at_index(second);
+            let r = tail.nonempty().unwrap().head();
+
+            let mut seen = std::collections::HashSet::new();
+            seen.insert(s[r].to_vec());
+
+            while s.next_permutation(r) {
+                assert!(seen.insert(s[r].to_vec()));
+            }
+
+            assert_eq!(seen.len(), 24);
+
+            // Wrapped around to the first permutation, without touching the surrounding elements.
+            assert_eq!(s[..], [9, 0, 1, 2, 3, 9]);
+        });
+    }
+
+    #[test]
+    fn prev_permutation_enumerates_all() {
+        let mut v = vec![3, 2, 1, 0];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range();
+            let mut count = 1;
+            let mut previous = s[..].to_vec();
+
+            while s.prev_permutation(r) {
+                assert!(s[..] < previous[..]);
+                previous = s[..].to_vec();
+                count += 1;
+            }
+
+            assert_eq!(count, 24);
+            assert_eq!(s[..], [3, 2, 1, 0]);
+        });
+    }
+
+    #[test]
+    fn permutation_wraps_around() {
+        let mut v = vec![1, 3, 2];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range();
+
+            assert!(s.next_permutation(r));
+            assert_eq!(s[..], [2, 1, 3]);
+
+            assert!(s.prev_permutation(r));
+            assert_eq!(s[..], [1, 3, 2]);
+
+            for _ in 0..4 {
+                assert!(s.next_permutation(r));
+            }
+            assert_eq!(s[..], [3, 2, 1]);
+
+            assert!(!s.next_permutation(r));
+            assert_eq!(s[..], [1, 2, 3]);
+
+            assert!(!s.prev_permutation(r));
+            assert_eq!(s[..], [3, 2, 1]);
+        });
+    }
+
+    #[test]
+    fn permutation_short_ranges() {
+        let mut empty: Vec<u8> = vec![];
+        let mut single = vec![1];
+
+        region(empty.as_mut_slice(), |mut s| {
+            let r = s.range();
+            assert!(!s.next_permutation(r));
+            assert!(!s.prev_permutation(r));
+        });
+
+        region(single.as_mut_slice(), |mut s| {
+            let r = s.range();
+            assert!(!s.next_permutation(r));
+            assert!(!s.prev_permutation(r));
+            assert_eq!(s[..], [1]);
+        });
+    }
+
+    #[test]
+    fn get() {
+        let mut v = vec![1, 2, 3];
+
+        region(v.as_mut_slice(), |mut s| {
+            assert_eq!(s.get(0), Some(&1));
+            assert_eq!(s.get(2), Some(&3));
+            assert_eq!(s.get(3), None);
+            assert_eq!(s.get(usize::MAX), None);
+
+            *s.get_mut(1).unwrap() = 20;
+            assert!(s.get_mut(3).is_none());
+
+            // Untrusted and trusted accesses mix within the same region.
+            let last = s.range().nonempty().unwrap().last();
+            assert_eq!(s[last], 3);
+            assert_eq!(s[..], [1, 20, 3]);
+        });
+    }
+
+    #[test]
+    fn vet() {
+        let mut v = vec![10, 20, 30];
+
+        region(v.as_mut_slice(), |mut s| {
+            let i = s.vet(1).unwrap();
+            s[i] += 1;
+            s[i] += 1;
+            assert_eq!(s[i], 22);
+
+            let err = s.vet(3).err().unwrap();
+            assert_eq!((err.index(), err.length()), (3, 3));
+            assert_eq!(
+                err.to_string(),
+                "index out of bounds: the len is 3 but the index is 3"
+            );
+        });
+    }
+
+    #[test]
+    fn vet_adjacency() {
+        // Check once, use many times: vet every edge of a graph up front.
+        let mut visited = vec![false; 4];
+        let edges = [(0, 1), (1, 2), (2, 0), (3, 3)];
+
+        region(visited.as_mut_slice(), |mut s| {
+            let vetted: Vec<_> = edges
+                .iter()
+                .map(|&(a, b)| (s.vet(a).unwrap(), s.vet(b).unwrap()))
+                .collect();
+
+            let mut stack = vec![vetted[0].0];
+            while let Some(node) = stack.pop() {
+                if !s[node] {
+                    s[node] = true;
+                    stack.extend(vetted.iter().filter(|e| e.0 == node).map(|e| e.1));
+                }
+            }
+        });
+
+        assert_eq!(visited, [true, true, true, false]);
+    }
+
+    #[test]
+    fn vet_range() {
+        use crate::container::error::RangeError;
+
+        let mut v = vec![1, 2, 3, 4, 5];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range(1, 4).unwrap();
+            assert_eq!(s[r], [2, 3, 4]);
+
+            for x in &mut s[r] {
+                *x *= 10;
+            }
+            assert_eq!(s[r], [20, 30, 40]);
+
+            assert!(s.vet_range(5, 5).unwrap().is_empty());
+            assert!(s.vet_range(0, 5).is_ok());
+
+            assert_eq!(
+                s.vet_range(3, 2).err(),
+                Some(RangeError::Inverted { start: 3, end: 2 })
+            );
+            assert_eq!(
+                s.vet_range(2, 6).err(),
+                Some(RangeError::EndOutOfBounds { end: 6, len: 5 })
+            );
+        });
+    }
+
+    #[test]
+    fn vet_range_nonempty() {
+        use crate::container::error::RangeError;
+
+        let mut v = vec![1, 2, 3];
+
+        region(v.as_mut_slice(), |s| {
+            let r = s.vet_range_nonempty(1, 3).unwrap();
+            assert_eq!(s[r.first()], 2);
+            assert_eq!(s[r.last()], 3);
+
+            assert_eq!(
+                s.vet_range_nonempty(2, 2).err(),
+                Some(RangeError::Empty { at: 2 })
+            );
+            assert_eq!(
+                s.vet_range_nonempty(4, 2).err(),
+                Some(RangeError::Inverted { start: 4, end: 2 })
+            );
+        });
+    }
+
+    #[test]
+    fn first_and_last_of() {
+        let mut v = vec![1, 2, 3];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range().nonempty().unwrap();
+
+            assert_eq!(s.first_of(r), &1);
+            assert_eq!(s.last_of(r), &3);
+
+            *s.first_of_mut(r) = 10;
+            *s.last_of_mut(r) = 30;
+
+            assert_eq!(s.first(), Some(&10));
+            assert_eq!(s.last(), Some(&30));
+        });
+    }
+
+    #[test]
+    fn first_and_last_empty() {
+        let mut v: Vec<i32> = vec![];
+
+        region(v.as_mut_slice(), |s| {
+            assert_eq!(s.first(), None);
+            assert_eq!(s.last(), None);
+        });
+    }
+
+    #[test]
+    fn first_and_last_single() {
+        let mut v = vec![1];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range().nonempty().unwrap();
+            assert!(core::ptr::eq(s.first_of(r), s.last_of(r)));
+
+            *s.first_of_mut(r) += 1;
+            *s.last_of_mut(r) += 1;
+
+            assert_eq!(s.first(), Some(&3));
+            assert_eq!(s.last(), Some(&3));
+        });
+    }
+
+    #[test]
+    fn iter() {
+        let mut v = vec![1, 2, 3];
+
+        region(v.as_mut_slice(), |mut s| {
+            for x in s.iter_mut() {
+                *x *= 2;
+            }
+
+            assert_eq!(s.iter().sum::<i32>(), 12);
+            assert_eq!(s.iter().rev().copied().collect::<Vec<_>>(), [6, 4, 2]);
+        });
+    }
+
+    #[test]
+    fn iter_indexed() {
+        let mut v = vec![1, 2, 3, 4, 5, 6];
+
+        region(v.as_mut_slice(), |mut s| {
+            let even: Vec<_> = s
+                .iter_indexed()
+                .filter(|(_, x)| *x % 2 == 0)
+                .map(|(i, _)| i)
+                .collect();
+
+            for i in even {
+                s[i] = 0;
+            }
+
+            assert_eq!(s[..], [1, 0, 3, 0, 5, 0]);
+
+            let mut iter = s.iter_indexed();
+            assert_eq!(iter.len(), 6);
+
+            let (last, x) = iter.next_back().unwrap();
+            assert_eq!((last.integer(), *x), (5, 0));
+        });
+    }
+
+    #[test]
+    fn iter_mut_indexed() {
+        let mut v = vec![5, 5, 5, 5];
+
+        region(v.as_mut_slice(), |mut s| {
+            for (i, x) in s.iter_mut_indexed() {
+                *x *= i.integer();
+            }
+
+            let (first, x) = s.iter_mut_indexed().rev().last().unwrap();
+            *x = 100;
+            assert_eq!(first.integer(), 0);
+        });
+
+        assert_eq!(v, [100, 5, 10, 15]);
+    }
+
+    #[test]
+    fn swap_ranges_adjacent() {
+        let mut v = vec![1, 2, 3, 4, 5, 6];
+
+        region(v.as_mut_slice(), |mut s| {
+            let (a, b) = s.range().split_in_half();
+            s.swap_ranges(a.nonempty().unwrap(), b.nonempty().unwrap());
+        });
+
+        assert_eq!(v, [4, 5, 6, 1, 2, 3]);
+    }
+
+    #[test]
+    fn swap_ranges_at_the_ends() {
+        let mut v = vec![1, 2, 3, 4, 5, 6, 7];
+
+        region(v.as_mut_slice(), |mut s| {
+            let a = s.vet_range_nonempty(0, 2).unwrap();
+            let b = s.vet_range_nonempty(5, 7).unwrap();
+            s.swap_ranges(a, b);
+        });
+
+        assert_eq!(v, [6, 7, 3, 4, 5, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "swap_ranges: ranges 0..3 and 2..5 overlap")]
+    fn swap_ranges_overlap() {
+        let mut v = vec![1, 2, 3, 4, 5];
+
+        region(v.as_mut_slice(), |mut s| {
+            let a = s.vet_range_nonempty(0, 3).unwrap();
+            let b = s.vet_range_nonempty(2, 5).unwrap();
+            s.swap_ranges(a, b);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "swap_ranges: ranges differ in length (1 and 2)")]
+    fn swap_ranges_length_mismatch() {
+        let mut v = vec![1, 2, 3];
+
+        region(v.as_mut_slice(), |mut s| {
+            let a = s.vet_range_nonempty(0, 1).unwrap();
+            let b = s.vet_range_nonempty(1, 3).unwrap();
+            s.swap_ranges(a, b);
+        });
+    }
+
+    #[test]
+    fn reverse_agrees_with_slice_reverse() {
+        let mut seed = 0x1234_5678_9abc_def0;
+
+        for len in 0..24 {
+            let mut v: Vec<u64> = (0..len).collect();
+
+            for _ in 0..8 {
+                let a = (xorshift(&mut seed) % (len + 1)) as usize;
+                let b = (xorshift(&mut seed) % (len + 1)) as usize;
+                let (start, end) = (a.min(b), a.max(b));
+
+                let mut expected = v.clone();
+                expected[start..end].reverse();
+
+                region(v.as_mut_slice(), |mut s| {
+                    let r = s.vet_range(start, end).unwrap();
+                    s.reverse(r);
+                });
+
+                assert_eq!(v, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn reverse_short_ranges() {
+        let mut v = vec![1, 2, 3];
+
+        region(v.as_mut_slice(), |mut s| {
+            let empty = s.vet_range(1, 1).unwrap();
+            let single = s.vet_range(1, 2).unwrap();
+
+            s.reverse(empty);
+            s.reverse(single);
+        });
+
+        assert_eq!(v, [1, 2, 3]);
+    }
+
+    #[test]
+    fn rotate_range() {
+        let mut v = vec![0, 1, 2, 3, 4, 5, 6];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range_nonempty(1, 6).unwrap();
+
+            s.rotate_range_left(r, 2);
+            assert_eq!(s[..], [0, 3, 4, 5, 1, 2, 6]);
+
+            s.rotate_range_right(r, 2);
+            assert_eq!(s[..], [0, 1, 2, 3, 4, 5, 6]);
+
+            // mid == 0 and mid == len are both no-ops.
+            s.rotate_range_left(r, 0);
+            s.rotate_range_left(r, 5);
+            s.rotate_range_right(r, 0);
+            s.rotate_range_right(r, 5);
+            assert_eq!(s[..], [0, 1, 2, 3, 4, 5, 6]);
+
+            // mid is reduced modulo the length.
+            s.rotate_range_left(r, 7);
+            assert_eq!(s[..], [0, 3, 4, 5, 1, 2, 6]);
+            s.rotate_range_right(r, 12);
+            assert_eq!(s[..], [0, 1, 2, 3, 4, 5, 6]);
+        });
+    }
+
+    #[test]
+    fn rotate_range_drop() {
+        use std::rc::Rc;
+
+        let values: Vec<Rc<u32>> = (0..5).map(Rc::new).collect();
+        let mut v = values.clone();
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range().nonempty().unwrap();
+            s.rotate_range_left(r, 3);
+            s.rotate_range_right(r, 1);
+        });
+
+        assert_eq!(v.iter().map(|x| **x).collect::<Vec<_>>(), [2, 3, 4, 0, 1]);
+        drop(v);
+
+        assert!(values.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+
+    #[test]
+    fn copy_within() {
+        let mut v = vec![1, 2, 3, 4, 5, 6];
+
+        region(v.as_mut_slice(), |mut s| {
+            let src = s.vet_range(0, 3).unwrap();
+            let dest = s.vet(3).unwrap();
+            s.copy_within(src, dest);
+            assert_eq!(s[..], [1, 2, 3, 1, 2, 3]);
+
+            // Overlapping source and destination.
+            let src = s.vet_range(0, 4).unwrap();
+            let dest = s.vet(1).unwrap();
+            s.copy_within(src, dest);
+            assert_eq!(s[..], [1, 1, 2, 3, 1, 3]);
+
+            let src = s.vet_range(2, 6).unwrap();
+            let dest = s.vet(0).unwrap();
+            s.copy_within(src, dest);
+            assert_eq!(s[..], [2, 3, 1, 3, 1, 3]);
+        });
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "copy_within: destination 4..7 is out of bounds for a container of length 6"
+    )]
+    fn copy_within_out_of_bounds() {
+        let mut v = vec![1, 2, 3, 4, 5, 6];
+
+        region(v.as_mut_slice(), |mut s| {
+            let src = s.vet_range(0, 3).unwrap();
+            let dest = s.vet(4).unwrap();
+            s.copy_within(src, dest);
+        });
+    }
+
+    #[test]
+    fn fill() {
+        let mut v = vec![1, 2, 3, 4, 5];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range(1, 4).unwrap();
+            s.fill(r, 0);
+        });
+
+        assert_eq!(v, [1, 0, 0, 0, 5]);
+    }
+
+    #[test]
+    fn fill_empty_does_not_clone() {
+        #[derive(PartialEq, Debug)]
+        struct NoClone(u8);
+
+        impl Clone for NoClone {
+            fn clone(&self) -> Self {
+                panic!("cloned");
+            }
+        }
+
+        let mut v = vec![NoClone(1), NoClone(2)];
+
+        region(v.as_mut_slice(), |mut s| {
+            let empty = s.vet_range(1, 1).unwrap();
+            s.fill(empty, NoClone(0));
+
+            // A single element range moves the value in, without cloning.
+            let single = s.vet_range(0, 1).unwrap();
+            s.fill(single, NoClone(0));
+        });
+
+        assert_eq!(v, [NoClone(0), NoClone(2)]);
+    }
+
+    #[test]
+    fn copy_from() {
+        let mut input = vec![1, 2, 3, 4];
+        let mut output = vec![0; 4];
+
+        region(input.as_mut_slice(), |input| {
+            region(output.as_mut_slice(), |mut output| {
+                let proof = output.len_eq(&input).unwrap();
+                output.copy_from(&input, proof);
+            })
+        });
+
+        assert_eq!(output, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn len_eq_mismatch() {
+        let mut a = vec![1, 2, 3];
+        let mut b = vec![1, 2];
+
+        region(a.as_mut_slice(), |a| {
+            region(b.as_mut_slice(), |b| {
+                assert!(a.len_eq(&b).is_none());
+            })
+        });
+    }
+
+    #[test]
+    fn copy_range_from() {
+        let mut input = vec![1, 2, 3, 4];
+        let mut output = vec![0; 6];
+
+        region(input.as_mut_slice(), |input| {
+            region(output.as_mut_slice(), |mut output| {
+                let src = input.vet_range(1, 3).unwrap();
+                let dst = output.vet_range(3, 5).unwrap();
+                assert_eq!(output.copy_range_from(dst, &input, src), Ok(()));
+
+                let dst = output.vet_range(0, 3).unwrap();
+                let err = output.copy_range_from(dst, &input, src).unwrap_err();
+                assert_eq!((err.left(), err.right()), (3, 2));
+            })
+        });
+
+        assert_eq!(output, [0, 0, 0, 2, 3, 0]);
+    }
+
+    #[test]
+    fn get_pair_mut() {
+        let mut v = vec![1, 2, 3];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range().nonempty().unwrap();
+            let (first, last) = (r.first(), r.last());
+
+            let (a, b) = s.get_pair_mut(last, first).unwrap();
+            assert_eq!((*a, *b), (3, 1));
+
+            *a += 10;
+            *b += 20;
+
+            assert!(s.get_pair_mut(first, first).is_none());
+        });
+
+        assert_eq!(v, [21, 2, 13]);
+    }
+
+    #[test]
+    fn binary_search_agrees_with_slice() {
+        let mut seed = 0xdead_beef_cafe_f00d;
+
+        for len in 0..48 {
+            let mut v: Vec<u64> = (0..len).map(|_| xorshift(&mut seed) % 16).collect();
+            v.sort_unstable();
+
+            region(v.as_mut_slice(), |s| {
+                for x in 0..18 {
+                    match (s.binary_search(&x), s[..].binary_search(&x)) {
+                        (Ok(found), Ok(_)) => assert_eq!(s[found], x),
+                        (Err(a), Err(b)) => assert_eq!(a, b),
+                        (found, expected) => panic!(
+                            "searching {} in {:?}: {:?} vs {:?}",
+                            x,
+                            &s[..],
+                            found.map(|i| i.integer()),
+                            expected
+                        ),
+                    }
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn binary_search_by_key() {
+        let mut v = vec![(1, 'a'), (3, 'b'), (5, 'c')];
+
+        region(v.as_mut_slice(), |s| {
+            let found = s.binary_search_by_key(&3, |e| e.0).unwrap();
+            assert_eq!(s[found].1, 'b');
+
+            assert_eq!(s.binary_search_by_key(&4, |e| e.0).err(), Some(2));
+            assert_eq!(s.binary_search_by_key(&9, |e| e.0).err(), Some(3));
+        });
+    }
+
+    #[test]
+    fn binary_search_range_by() {
+        // Two records, each sorted on its own.
+        let mut v = vec![5, 6, 7, 1, 2, 3, 4];
+
+        region(v.as_mut_slice(), |s| {
+            let first = s.vet_range(0, 3).unwrap();
+            let second = s.vet_range(3, 7).unwrap();
+
+            let found = s.binary_search_range_by(second, |x| x.cmp(&2)).unwrap();
+            assert_eq!(found.integer(), 4);
+            assert_eq!(s[found], 2);
+
+            assert_eq!(
+                s.binary_search_range_by(first, |x| x.cmp(&2)).err(),
+                Some(0)
+            );
+            assert_eq!(
+                s.binary_search_range_by(first, |x| x.cmp(&9)).err(),
+                Some(3)
+            );
+
+            // A range at the very end of the container.
+            assert_eq!(
+                s.binary_search_range_by(second, |x| x.cmp(&9)).err(),
+                Some(7)
+            );
+
+            let empty = s.vet_range(2, 2).unwrap();
+            assert_eq!(
+                s.binary_search_range_by(empty, |x| x.cmp(&6)).err(),
+                Some(2)
+            );
+        });
+    }
+
+    #[test]
+    fn partition_point() {
+        let mut v = vec![9, 1, 3, 5, 7, 2, 4, 6, 0];
+
+        region(v.as_mut_slice(), |s| {
+            let r = s.vet_range(1, 8).unwrap();
+
+            let edge = s.partition_point(r, |x| x % 2 == 1);
+            assert_eq!(edge.integer(), 5);
+
+            let (odd, even) = s.split_at_index(edge);
+            assert_eq!(&s[odd], &[9, 1, 3, 5, 7]);
+            assert_eq!(&s[even], &[2, 4, 6, 0]);
+
+            // All elements satisfy the predicate.
+            assert_eq!(s.partition_point(r, |_| true).integer(), r.end());
+
+            // None of the elements satisfy the predicate.
+            assert_eq!(s.partition_point(r, |_| false).integer(), r.start());
+
+            let empty = s.vet_range(4, 4).unwrap();
+            assert_eq!(s.partition_point(empty, |_| true).integer(), 4);
+        });
+    }
+
+    #[test]
+    fn lower_upper_bound() {
+        let mut v = vec![0, 1, 3, 3, 3, 5, 8, 0];
+
+        region(v.as_mut_slice(), |s| {
+            let r = s.vet_range(1, 7).unwrap();
+
+            assert_eq!(s.lower_bound(r, &3).integer(), 2);
+            assert_eq!(s.upper_bound(r, &3).integer(), 5);
+
+            assert_eq!(s.lower_bound(r, &4).integer(), 5);
+            assert_eq!(s.upper_bound(r, &4).integer(), 5);
+
+            assert_eq!(s.lower_bound(r, &0).integer(), r.start());
+            assert_eq!(s.upper_bound(r, &9).integer(), r.end());
+
+            let empty = s.vet_range(3, 3).unwrap();
+            assert_eq!(s.lower_bound(empty, &0).integer(), 3);
+            assert_eq!(s.upper_bound(empty, &9).integer(), 3);
+
+            let (lhs, rhs) = s.split_at_index(s.upper_bound(r, &3));
+            assert_eq!(&s[lhs], &[0, 1, 3, 3, 3]);
+            assert_eq!(&s[rhs], &[5, 8, 0]);
+        });
+    }
+
+    #[test]
+    fn equal_range() {
+        let mut seed = 0x5eed_u64;
+
+        for len in 0..64 {
+            let mut v = (0..len)
+                .map(|_| xorshift(&mut seed) % 6)
+                .collect::<Vec<_>>();
+            v.sort();
+
+            for x in 0..7 {
+                let expected = match v.iter().position(|&e| e == x) {
+                    Some(start) => start..v.iter().rposition(|&e| e == x).unwrap() + 1,
+                    None => {
+                        let at = v.iter().position(|&e| e > x).unwrap_or(len);
+                        at..at
+                    }
+                };
+
+                region(v.as_mut_slice(), |s| {
+                    let found = s.equal_range(s.range(), &x);
+                    assert_eq!(found.start()..found.end(), expected);
+                    assert!(s[found].iter().all(|&e| e == x));
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn position_find() {
+        let mut v = vec![1, 3, 5, 6, 7, 8];
+
+        region(v.as_mut_slice(), |s| {
+            let index = s.position(|x| x % 2 == 0).unwrap();
+            assert_eq!(index.integer(), 3);
+
+            let (lhs, rhs) = s.split_at_index(index);
+            assert_eq!(&s[lhs], &[1, 3, 5]);
+            assert_eq!(&s[rhs], &[6, 7, 8]);
+
+            let (found, item) = s.find(|&x| x > 6).unwrap();
+            assert!(found.integer() == 4 && *item == 7);
+            assert!(s.find(|&x| x > 8).is_none());
+            assert!(s.position(|&x| x > 8).is_none());
+        });
+    }
+
+    #[test]
+    fn rposition() {
+        let mut bytes = vec![7u8, 0, 3, 0, 0];
+
+        region(bytes.as_mut_slice(), |s| {
+            let last = s.rposition(|&b| b != 0).unwrap();
+            assert_eq!(last.integer(), 2);
+
+            let (trimmed, _) = s.split_at_index(last.after());
+            assert_eq!(&s[trimmed], &[7, 0, 3]);
+
+            // Hits on the first and on the last element.
+            assert_eq!(s.rposition(|&b| b == 7).unwrap().integer(), 0);
+            assert_eq!(s.rposition(|&b| b == 0).unwrap().integer(), 4);
+
+            assert!(s.rposition(|&b| b == 9).is_none());
+        });
+
+        region(Vec::<u8>::new(), |s| {
+            assert!(s.rposition(|_| true).is_none());
+        });
+    }
+
+    #[test]
+    fn index_of() {
+        let mut bytes = (0..64u8).rev().collect::<Vec<_>>();
+
+        region(bytes.as_mut_slice(), |s| {
+            let sentinel = s.index_of(&0).unwrap();
+            assert_eq!(sentinel.integer(), 63);
+            assert_eq!(s[sentinel], 0);
+            assert_eq!(s.index_of(&40).unwrap().integer(), 23);
+            assert!(s.index_of(&64).is_none());
+        });
+
+        let mut signed = vec![3i8, -1, 5, -1];
+        region(signed.as_mut_slice(), |s| {
+            assert_eq!(s.index_of(&-1).unwrap().integer(), 1);
+            assert!(s.index_of(&-2).is_none());
+        });
+
+        let mut words = vec!["a", "b", "c"];
+        region(words.as_mut_slice(), |s| {
+            assert_eq!(s.index_of(&"c").unwrap().integer(), 2);
+            assert!(s.index_of(&"d").is_none());
+        });
+    }
+
+    #[test]
+    fn min_max_index() {
+        let mut v = vec![4, 1, 7, 1, 7, 3];
+
+        region(v.as_mut_slice(), |s| {
+            let r = s.range();
+
+            // Ties resolve to the first occurrence.
+            assert_eq!(s.min_index(r).unwrap().integer(), 1);
+            assert_eq!(s.max_index(r).unwrap().integer(), 2);
+
+            let tail = s.vet_range(3, 6).unwrap();
+            assert_eq!(s.min_index(tail).unwrap().integer(), 3);
+            assert_eq!(s.max_index(tail).unwrap().integer(), 4);
+
+            assert_eq!(
+                s.min_index_by_key(r, |x| (x - 4i32).abs())
+                    .unwrap()
+                    .integer(),
+                0
+            );
+            assert_eq!(s.max_index_by(r, |a, b| b.cmp(a)).unwrap().integer(), 1);
+
+            let empty = s.vet_range(2, 2).unwrap();
+            assert!(s.min_index(empty).is_none());
+            assert!(s.max_index_by_key(empty, |&x| x).is_none());
+        });
+    }
+
+    #[test]
+    fn selection_sort() {
+        let mut seed = 0xabcd_u64;
+        let mut v = (0..32)
+            .map(|_| xorshift(&mut seed) % 10)
+            .collect::<Vec<_>>();
+
+        let mut expected = v.clone();
+        expected.sort();
+
+        region(v.as_mut_slice(), |mut s| {
+            let mut rest = s.range();
+
+            while let Some(nonempty) = rest.nonempty() {
+                let min = s.min_index(nonempty).unwrap();
+                s.swap(nonempty.first(), min);
+                rest = nonempty.tail();
+            }
+        });
+
+        assert_eq!(v, expected);
+    }
+
+    #[test]
+    fn is_sorted() {
+        let mut v = vec![1, 2, 2, 2, 5, 9, 8];
+
+        region(v.as_mut_slice(), |s| {
+            assert!(s.is_sorted(s.vet_range(3, 3).unwrap()));
+            assert!(s.is_sorted(s.vet_range(6, 7).unwrap()));
+            assert!(s.is_sorted(s.vet_range(0, 6).unwrap()));
+
+            // The only out-of-order pair is at the very end.
+            assert!(!s.is_sorted(s.range()));
+            assert!(s.is_sorted_by(s.vet_range(5, 7).unwrap(), |a, b| a >= b));
+        });
+    }
+
+    #[test]
+    fn partition_in_place() {
+        let mut v = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range(1, 9).unwrap();
+
+            // Alternating.
+            let odd = s.partition_in_place(r, |x| x % 2 == 1);
+            assert!(odd.start() == 1 && odd.end() == 5);
+            assert!(s[odd].iter().all(|x| x % 2 == 1));
+
+            let (_, rest) = r.split_index(s.vet(odd.end()).unwrap());
+            assert!(s[rest].iter().all(|x| x % 2 == 0));
+
+            // All true.
+            let all = s.partition_in_place(r, |_| true);
+            assert!(all.start() == 1 && all.end() == 9);
+
+            // All false.
+            let none = s.partition_in_place(r, |_| false);
+            assert!(none.start() == 1 && none.end() == 1);
+
+            assert_eq!(s[s.vet(0).unwrap()], 0);
+            assert_eq!(s[s.vet(9).unwrap()], 9);
+        });
+
+        let mut seed = 0xfeed_u64;
+        for len in 0..48 {
+            let mut v = (0..len)
+                .map(|_| xorshift(&mut seed) % 4)
+                .collect::<Vec<_>>();
+            let mut expected = v.clone();
+            expected.sort();
+
+            region(v.as_mut_slice(), |mut s| {
+                let small = s.partition_in_place(s.range(), |&x| x < 2);
+                assert_eq!(small.len(), expected.iter().filter(|&&x| x < 2).count());
+                assert!(s.is_partitioned(s.range(), |&x| x < 2));
+            });
+
+            v.sort();
+            assert_eq!(v, expected);
+        }
+    }
+
+    #[test]
+    fn select_nth_unstable() {
+        let mut seed = 0x1234_5678_u64;
+
+        for len in 1..64 {
+            for &modulus in &[3, 1000] {
+                let mut v = (0..len)
+                    .map(|_| xorshift(&mut seed) % modulus)
+                    .collect::<Vec<_>>();
+                let mut sorted = v.clone();
+                sorted.sort();
+
+                for (n, &expected) in sorted.iter().enumerate() {
+                    region(v.as_mut_slice(), |mut s| {
+                        let r = s.range().nonempty().unwrap();
+                        let (before, nth, after) = s.select_nth_unstable(r, n);
+
+                        assert_eq!(nth.integer(), n);
+                        assert_eq!(s[nth], expected);
+                        assert!(s[before].iter().all(|&x| x <= expected));
+                        assert!(s[after].iter().all(|&x| x >= expected));
+                        assert!(before.len() + 1 + after.len() == len as usize);
+                    });
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn select_nth_unstable_subrange() {
+        let mut v = vec![9, 5, 4, 3, 2, 1, 0];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range_nonempty(1, 6).unwrap();
+            let (before, nth, after) = s.select_nth_unstable_by(r, 1, |a, b| b.cmp(a));
+
+            assert_eq!(s[nth], 4);
+            assert!(before.start() == 1 && after.end() == 6);
+        });
+
+        assert_eq!(v[0], 9);
+        assert_eq!(v[6], 0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "select_nth_unstable: index 3 is out of bounds for a range of length 3"
+    )]
+    fn select_nth_unstable_out_of_bounds() {
+        region(vec![1, 2, 3], |mut s| {
+            let r = s.range().nonempty().unwrap();
+            s.select_nth_unstable(r, 3);
+        });
+    }
+
+    #[test]
+    fn sort_range_unstable() {
+        let mut seed = 0x5047_u64;
+
+        for &len in &[0, 1, 2, 15, 16, 17, 100, 1000] {
+            let random = (0..len)
+                .map(|_| xorshift(&mut seed) % 10_000)
+                .collect::<Vec<_>>();
+            let few_unique = (0..len)
+                .map(|_| xorshift(&mut seed) % 3)
+                .collect::<Vec<_>>();
+            let ascending = (0..len).collect::<Vec<_>>();
+            let descending = (0..len).rev().collect::<Vec<_>>();
+            let organ_pipe = (0..len)
+                .map(|i| if i < len / 2 { i } else { len - i })
+                .collect::<Vec<_>>();
+
+            for input in [random, few_unique, ascending, descending, organ_pipe] {
+                let mut expected = input.clone();
+                expected.sort_unstable_by(|a, b| b.cmp(a));
+
+                let mut v = input.clone();
+                region(v.as_mut_slice(), |mut s| {
+                    s.sort_range_unstable_by(s.range(), |a, b| b.cmp(a))
+                });
+                assert_eq!(v, expected);
+
+                let mut v = input;
+                region(v.as_mut_slice(), |mut s| s.sort_range_unstable(s.range()));
+                expected.reverse();
+                assert_eq!(v, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn sort_range_unstable_subrange() {
+        let mut v = vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 0];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range(2, 8).unwrap();
+            s.sort_range_unstable(r);
+        });
+
+        assert_eq!(v, [9, 8, 2, 3, 4, 5, 6, 7, 1, 0]);
+    }
+
+    #[test]
+    fn heapsort() {
+        let mut seed = 0x4ea9_u64;
+
+        for len in 1..100 {
+            let mut v = (0..len)
+                .map(|_| xorshift(&mut seed) % 50)
+                .collect::<Vec<_>>();
+            let mut expected = v.clone();
+            expected.sort_unstable();
+
+            region(v.as_mut_slice(), |mut s| {
+                let r = s.range().nonempty().unwrap();
+                s.heapsort(r, &mut u64::cmp);
+            });
+
+            assert_eq!(v, expected);
+        }
+    }
+
+    #[test]
+    fn small_sort() {
+        let mut seed = 0x51_u64;
+
+        for len in 1..=SMALL_SORT_THRESHOLD {
+            for _ in 0..20 {
+                let mut v = (0..len)
+                    .map(|_| xorshift(&mut seed) % 8)
+                    .collect::<Vec<_>>();
+                let mut expected = v.clone();
+                expected.sort_unstable();
+
+                region(v.as_mut_slice(), |mut s| {
+                    let r = s.range().nonempty().unwrap();
+                    s.small_sort(r, &mut u64::cmp);
+                });
+
+                assert_eq!(v, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn introsort_heapsort_fallback() {
+        let mut seed = 0x9e37_79b9_7f4a_7c15_u64;
+        let mut v = (0..500)
+            .map(|_| xorshift(&mut seed) % 50)
+            .collect::<Vec<_>>();
+        let mut expected = v.clone();
+        expected.sort_unstable();
+
+        // Without any depth left, the whole range is sorted with heapsort.
+        region(v.as_mut_slice(), |mut s| {
+            s.introsort(s.range().forget_proof(), None, &mut u64::cmp, 0)
+        });
+
+        assert_eq!(v, expected);
+    }
+
+    #[test]
+    fn sort_range_unstable_drop() {
+        use std::cell::Cell;
+        use std::panic::{self, AssertUnwindSafe};
+
+        struct Tracked<'a>(u64, &'a Cell<usize>);
+
+        impl Drop for Tracked<'_> {
+            fn drop(&mut self) {
+                self.1.set(self.1.get() + 1);
+            }
+        }
+
+        let mut seed = 0x7e57_u64;
+
+        for &len in &[5, 17, SMALL_SORT_THRESHOLD, 100] {
+            let drops = Cell::new(0);
+            let mut v = (0..len)
+                .map(|_| Tracked(xorshift(&mut seed) % 100, &drops))
+                .collect::<Vec<_>>();
+
+            // A comparator that is not a total order must not duplicate or lose any element.
+            region(v.as_mut_slice(), |mut s| {
+                s.sort_range_unstable_by(s.range(), |_, _| {
+                    if xorshift(&mut seed) & 1 == 0 {
+                        core::cmp::Ordering::Less
+                    } else {
+                        core::cmp::Ordering::Greater
+                    }
+                })
+            });
+
+            // Neither must a comparator that panics halfway through, wherever that is.
+            let mut keys = v.iter().map(|t| t.0).collect::<Vec<_>>();
+            keys.sort_unstable();
+
+            for give_up in (0..).step_by(len / 4 + 1) {
+                let mut calls = 0;
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    region(v.as_mut_slice(), |mut s| {
+                        s.sort_range_unstable_by(s.range(), |a, b| {
+                            calls += 1;
+                            assert!(calls < give_up, "comparator gave up");
+                            a.0.cmp(&b.0)
+                        })
+                    })
+                }));
+
+                let mut after = v.iter().map(|t| t.0).collect::<Vec<_>>();
+                after.sort_unstable();
+                assert_eq!(after, keys);
+
+                if result.is_ok() {
+                    break;
+                }
+            }
+
+            assert_eq!(drops.get(), 0);
+            drop(v);
+            assert_eq!(drops.get(), len);
+        }
+    }
+
+    #[test]
+    fn split_around() {
+        let mut v = vec![3, 1, 4, 1, 5];
+
+        region(v.as_mut_slice(), |s| {
+            let (lhs, pivot, rhs) = s.split_around(s.vet(2).unwrap());
+            assert_eq!(&s[lhs], &[3, 1]);
+            assert_eq!(s[pivot], 4);
+            assert_eq!(&s[rhs], &[1, 5]);
+
+            let (lhs, _, rhs) = s.split_around(s.vet(4).unwrap());
+            assert_eq!(lhs.len(), 4);
+            assert!(rhs.is_empty() && rhs.start() == 5);
+
+            let (lhs, _, _) = s.split_around(s.vet(0).unwrap());
+            assert!(lhs.is_empty());
+        });
+    }
+
+    #[test]
+    fn split_at_with() {
+        let mut v = vec![1, 2, 3, 4, 5];
+
+        region(v.as_mut_slice(), |s| {
+            let mid = s.range().nonempty().unwrap().upper_middle();
+
+            let (first, last) = s.split_at_with(mid, |lhs, rhs, range, _| {
+                // `range` is NonEmpty, so no check is needed to access its first element.
+                (lhs.len(), rhs[range.first()])
+            });
+            assert_eq!((first, last), (2, 3));
+
+            let end = s.range().nonempty().unwrap().last().after();
+            s.split_at_with(end, |lhs, rhs, range, _| {
+                assert_eq!(lhs.len(), 5);
+                assert!(rhs.is_empty() && range.is_empty());
+            });
+        });
+    }
+
+    #[test]
+    fn split_at_mut_with() {
+        use super::{Container, Contract, NonEmpty, Range};
+
+        fn double_all<C: for<'s> Contract<'s>>(
+            mut s: Container<C, &mut [u32]>,
+            r: Range<C, NonEmpty>,
+        ) -> u32 {
+            let mid = r.upper_middle();
+
+            if mid == r.first() {
+                s[mid] *= 2;
+                return s[mid];
+            }
+
+            // The range of the latter half is known to be NonEmpty, only the former half is checked.
+            s.split_at_mut_with(mid, |lhs, rhs, rhs_range, _| {
+                let lhs_range = lhs.range().nonempty().unwrap();
+
+                double_all(lhs, lhs_range) + double_all(rhs, rhs_range)
+            })
+        }
+
+        let mut v = vec![1, 2, 3, 4, 5];
+        let total = region(v.as_mut_slice(), |s| {
+            let r = s.range().nonempty().unwrap();
+            double_all(s, r)
+        });
+
+        assert_eq!(total, 30);
+        assert_eq!(v, [2, 4, 6, 8, 10]);
+    }
+
+    #[test]
+    fn split_at_edges() {
+        use crate::container::error::OutOfBounds;
+
+        let mut v = vec![1, 2, 3];
+
+        region(v.as_mut_slice(), |mut s| {
+            let (lhs, rhs) = s.split_at(s.start_edge());
+            assert!(lhs.is_empty() && rhs == [1, 2, 3]);
+
+            let (lhs, rhs) = s.split_at(s.end_edge());
+            assert!(lhs == [1, 2, 3] && rhs.is_empty());
+
+            let end = s.end_edge();
+            let (lhs, rhs) = s.split_at_mut(end);
+            lhs[0] = 4;
+            assert!(rhs.is_empty());
+
+            let edge = s.vet_edge(3).unwrap();
+            assert!(edge == s.end_edge());
+            assert_eq!(s.vet_edge(4).err(), Some(OutOfBounds::new(4, 3)));
+
+            s.split_at_with(s.start_edge(), |lhs, rhs, range, _| {
+                assert!(lhs.is_empty());
+                assert_eq!(range.len(), rhs.len());
+            });
+        });
+
+        assert_eq!(v, [4, 2, 3]);
+    }
+
+    #[test]
+    fn for_each_chunk() {
+        let mut v = (1..=7).collect::<Vec<u32>>();
+
+        region(v.as_mut_slice(), |mut s| {
+            let mut sums = Vec::new();
+            s.for_each_chunk(3, |chunk| sums.push(chunk.iter().sum::<u32>()));
+            assert_eq!(sums, [6, 15, 7]);
+
+            s.for_each_chunk_mut(3, |mut chunk| {
+                // Every chunk, including the final short one, is non-empty.
+                let r = chunk.range().nonempty().unwrap();
+                chunk.reverse(r);
+            });
+        });
+
+        assert_eq!(v, [3, 2, 1, 6, 5, 4, 7]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn for_each_chunk_zero() {
+        region(vec![1, 2, 3], |s| s.for_each_chunk(0, |_| {}));
+    }
+
+    #[test]
+    fn split_translator() {
+        let mut v = vec![10, 20, 30, 40, 50];
+
+        region(v.as_mut_slice(), |mut s| {
+            let mid = s.vet(2).unwrap();
+            let low = s.index_of(&20).unwrap();
+            let high = s.index_of(&40).unwrap();
+
+            s.split_at_mut_with(mid, |mut lhs, mut rhs, _, t| {
+                assert_eq!(t.mid(), 2);
+                assert!(t.right(low).is_none() && t.left(high).is_none());
+
+                let l = t.left(low).unwrap();
+                let r = t.right(high).unwrap();
+                assert_eq!(r.integer(), 1);
+
+                lhs[l] += 1;
+                rhs[r] += 1;
+
+                assert!(t.from_left(l) == low);
+                assert!(t.from_right(r) == high);
+                assert!(t.right(mid).unwrap().integer() == 0);
+            });
+
+            assert_eq!(s[low], 21);
+            assert_eq!(s[high], 41);
+        });
+    }
+
+    #[test]
+    fn range_of() {
+        let mut bytes = b"split  these words".to_vec();
+        let other = [1u8, 2, 3];
+
+        region(bytes.as_mut_slice(), |s| {
+            let words = s[s.range()]
+                .split(|&b| b == b' ')
+                .filter(|word| !word.is_empty())
+                .map(|word| s.range_of(word).unwrap())
+                .collect::<Vec<_>>();
+
+            assert_eq!(words.len(), 3);
+            assert!(words[1].start() == 7 && words[1].end() == 12);
+            assert_eq!(&s[words[2]], b"words");
+
+            // Empty sub-slices at the start and at the very end.
+            let whole = &s[s.range()];
+            let start = s.range_of(&whole[..0]).unwrap();
+            let end = s.range_of(&whole[whole.len()..]).unwrap();
+            assert!(start.start() == 0 && start.is_empty());
+            assert!(end.start() == 18 && end.is_empty());
+
+            assert!(s.range_of(&other).is_none());
+            assert!(s.range_of(&other[..0]).is_none());
+        });
+
+        let mut words = vec![1u32, 2, 3, 4];
+        region(words.as_mut_slice(), |s| {
+            let whole = &s[s.range()];
+            let r = s.range_of(&whole[1..3]).unwrap();
+            assert!(r.start() == 1 && r.end() == 3);
+        });
+
+        // A foreign slice of zero-sized elements is indistinguishable from a sub-slice.
+        let mut units = vec![(); 4];
+        region(units.as_mut_slice(), |s| {
+            let whole = &s[s.range()];
+            assert!(s.range_of(&whole[1..3]).is_none());
+            assert!(s.range_of(&[(); 3]).is_none());
+            assert!(s.range_of(&[(); 5]).is_none());
+        });
+    }
+
+    #[test]
+    fn as_slice() {
+        let mut v = vec![3, 1, 2];
+
+        region(v.as_mut_slice(), |mut s| {
+            assert_eq!(s.as_slice(), &[3, 1, 2]);
+            assert_eq!(s.as_ptr(), s.as_slice().as_ptr());
+
+            s.as_mut_slice().sort();
+            assert_eq!(s.as_slice(), &[1, 2, 3]);
+
+            unsafe { *s.as_mut_ptr() = 0 };
+            assert_eq!(s.first(), Some(&0));
+        });
+    }
+
+    #[test]
+    fn len_nonzero() {
+        let mut v = vec![1, 2, 3, 4];
+
+        region(v.as_mut_slice(), |s| {
+            let proof = s.vet_range_nonempty(1, 2).unwrap();
+            assert_eq!(s.len_nonzero(proof).get(), 4);
+        });
+    }
+
+    #[test]
+    fn group_by() {
+        let mut v = vec![1, 1, 2, 3, 3, 3, 1];
+
+        region(v.as_mut_slice(), |s| {
+            let runs = s
+                .group_by(|a, b| a == b)
+                .map(|run| (s[run.first()], run.len()))
+                .collect::<Vec<_>>();
+
+            assert_eq!(runs, [(1, 2), (2, 1), (3, 3), (1, 1)]);
+
+            let ascending = s
+                .group_by(|a, b| a <= b)
+                .map(|run| run.len())
+                .collect::<Vec<_>>();
+            assert_eq!(ascending, [6, 1]);
+
+            let everything = s.group_by(|_, _| true).collect::<Vec<_>>();
+            assert!(everything.len() == 1 && everything[0] == s.range());
+        });
+
+        region(Vec::<u8>::new(), |s| {
+            assert_eq!(s.group_by(|a, b| a == b).count(), 0);
+        });
+    }
+
+    #[test]
+    fn split_by() {
+        let mut v = b"\nab\n\ncd\n".to_vec();
+        let copy = v.clone();
+        let expected = copy.split(|&b| b == b'\n').collect::<Vec<_>>();
+
+        region(v.as_mut_slice(), |s| {
+            let lines = s.split_by(|&b| b == b'\n').collect::<Vec<_>>();
+            let slices = lines.iter().map(|&r| &s[r]).collect::<Vec<_>>();
+            assert_eq!(slices, expected);
+
+            assert!(lines[1].start() == 1 && lines[1].end() == 3);
+            assert!(lines[3].start() == 5 && lines[3].end() == 7);
+
+            let nonempty = s
+                .split_by_nonempty(|&b| b == b'\n')
+                .map(|r| (r.start(), r.len_nonzero().get()))
+                .collect::<Vec<_>>();
+            assert_eq!(nonempty, [(1, 2), (5, 2)]);
+
+            assert_eq!(s.split_by(|_| false).count(), 1);
+        });
+
+        region(Vec::<u8>::new(), |s| {
+            let all = s.split_by(|_| true).collect::<Vec<_>>();
+            assert!(all.len() == 1 && all[0].is_empty());
+            assert_eq!(s.split_by_nonempty(|_| true).count(), 0);
+        });
+    }
+
+    #[test]
+    fn windows() {
+        let mut v = vec![1, 2, 3, 4];
+        let three = NonZeroUsize::new(3).unwrap();
+
+        region(v.as_mut_slice(), |s| {
+            let sums = s
+                .windows(three)
+                .map(|w| (w.start(), s[w].iter().sum::<i32>()))
+                .collect::<Vec<_>>();
+            assert_eq!(sums, [(0, 6), (1, 9)]);
+
+            let mut windows = s.windows(NonZeroUsize::new(1).unwrap());
+            assert_eq!(windows.len(), 4);
+            assert_eq!(windows.next_back().map(|w| s[w.first()]), Some(4));
+            assert_eq!(windows.next().map(|w| s[w.first()]), Some(1));
+            assert_eq!(windows.len(), 2);
+
+            let whole = s.windows(NonZeroUsize::new(4).unwrap()).collect::<Vec<_>>();
+            assert!(whole.len() == 1 && whole[0] == s.range());
+
+            assert_eq!(s.windows(NonZeroUsize::new(5).unwrap()).len(), 0);
+        });
+
+        region(Vec::<u8>::new(), |s| {
+            assert_eq!(s.windows(three).count(), 0);
+        });
+    }
+
+    #[test]
+    fn chunks() {
+        let three = NonZeroUsize::new(3).unwrap();
+
+        // The length is not a multiple of the chunk size.
+        let mut v = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        region(v.as_mut_slice(), |s| {
+            let chunks = s.chunks(three).map(|c| &s[c]).collect::<Vec<_>>();
+            assert_eq!(chunks, [&[1, 2, 3][..], &[4, 5, 6], &[7, 8]]);
+
+            let mut iter = s.chunks(three);
+            assert_eq!(iter.len(), 3);
+            assert_eq!(iter.next_back().map(|c| &s[c]), Some(&[7, 8][..]));
+            assert_eq!(iter.next_back().map(|c| &s[c]), Some(&[4, 5, 6][..]));
+            assert_eq!(iter.len(), 1);
+            assert_eq!(iter.next().map(|c| &s[c]), Some(&[1, 2, 3][..]));
+            assert!(iter.next().is_none() && iter.next_back().is_none());
+        });
+
+        // The length is a multiple of the chunk size.
+        let mut v = vec![1, 2, 3, 4, 5, 6];
+        region(v.as_mut_slice(), |s| {
+            let lens = s
+                .chunks(three)
+                .rev()
+                .map(|c| (c.start(), c.len()))
+                .collect::<Vec<_>>();
+            assert_eq!(lens, [(3, 3), (0, 3)]);
+
+            let single = s.chunks(NonZeroUsize::new(10).unwrap()).collect::<Vec<_>>();
+            assert!(single.len() == 1 && single[0] == s.range());
+        });
+
+        region(Vec::<u8>::new(), |s| {
+            assert_eq!(s.chunks(three).len(), 0);
+        });
+    }
+
+    #[test]
+    fn rchunks() {
+        let three = NonZeroUsize::new(3).unwrap();
+
+        let mut v = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        let expected = v.rchunks(3).map(|c| c.to_vec()).collect::<Vec<_>>();
+
+        region(v.as_mut_slice(), |s| {
+            let chunks = s.rchunks(three).map(|c| s[c].to_vec()).collect::<Vec<_>>();
+            assert_eq!(chunks, expected);
+
+            // The short chunk lands at the front of the container.
+            let mut iter = s.rchunks(three);
+            assert_eq!(iter.len(), 3);
+            assert_eq!(iter.next_back().map(|c| &s[c]), Some(&[1, 2][..]));
+            assert_eq!(iter.next().map(|c| &s[c]), Some(&[6, 7, 8][..]));
+            assert_eq!(iter.next_back().map(|c| &s[c]), Some(&[3, 4, 5][..]));
+            assert!(iter.next().is_none());
+        });
+
+        let mut v = vec![1, 2, 3, 4, 5, 6];
+        region(v.as_mut_slice(), |s| {
+            let starts = s
+                .rchunks(three)
+                .map(|c| (c.start(), c.len()))
+                .collect::<Vec<_>>();
+            assert_eq!(starts, [(3, 3), (0, 3)]);
+        });
+    }
+
+    #[test]
+    fn find_byte() {
+        let mut text = b"first line\nsecond line\nthird".to_vec();
+
+        region(text.as_mut_slice(), |s| {
+            let newline = s.find_byte(s.range(), b'\n').unwrap();
+            assert_eq!(newline.integer(), 10);
+
+            let (first, _, rest) = s.split_around(newline);
+            assert_eq!(&s[first], b"first line");
+
+            let next = s.find_byte(rest, b'\n').unwrap();
+            assert_eq!(next.integer(), 22);
+            assert_eq!(s.find_byte_rev(s.range(), b'\n').unwrap().integer(), 22);
+            assert_eq!(s.find_byte_rev(first, b'i').unwrap().integer(), 7);
+
+            let (_, _, last) = s.split_around(next);
+            assert!(s.find_byte(last, b'\n').is_none());
+            assert!(s.find_byte_rev(last, b'\n').is_none());
+        });
+    }
+
+    #[test]
+    fn starts_ends_with() {
+        let mut v = b"HDR:body;HDR:tail".to_vec();
+
+        region(v.as_mut_slice(), |s| {
+            assert!(s.starts_with(b"HDR:") && s.ends_with(b"tail"));
+            assert!(!s.starts_with(b"tail") && !s.ends_with(b"HDR:"));
+            assert!(s.starts_with(b"") && s.ends_with(b""));
+
+            let second = s.vet_range(9, 17).unwrap();
+            assert!(s.range_starts_with(second, b"HDR:"));
+            assert!(s.range_ends_with(second, b":tail"));
+            assert!(!s.range_starts_with(second, b"body"));
+
+            // Needles longer than the range.
+            let short = s.vet_range(0, 3).unwrap();
+            assert!(!s.range_starts_with(short, b"HDR:"));
+            assert!(!s.range_ends_with(short, b"HDR:"));
+        });
+    }
+
+    #[test]
+    fn contains_value() {
+        let mut v = vec![1, 2, 3, 4];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range(1, 3).unwrap();
+            assert!(s.contains_value(r, &3));
+            assert!(!s.contains_value(r, &4));
+
+            // The match is the last element.
+            assert!(s.contains_value(s.range(), &4));
+
+            let empty = s.vet_range(2, 2).unwrap();
+            assert!(!s.contains_value(empty, &3));
+
+            if s.contains_value(r, &2) {
+                s.fill(r, 0);
+            }
+        });
+
+        assert_eq!(v, [1, 0, 0, 4]);
+
+        let mut bytes = b"abc\n".to_vec();
+        region(bytes.as_mut_slice(), |s| {
+            assert!(s.contains_value(s.range(), &b'\n'));
+            assert!(!s.contains_value(s.vet_range(0, 3).unwrap(), &b'\n'));
+        });
+    }
+
+    #[test]
+    fn eq_ranges() {
+        let mut v = b"abcabcab".to_vec();
+
+        region(v.as_mut_slice(), |s| {
+            let first = s.vet_range(0, 3).unwrap();
+            let second = s.vet_range(3, 6).unwrap();
+            assert!(s.eq_ranges(first, second));
+
+            // Overlapping ranges.
+            assert!(s.eq_ranges(s.vet_range(0, 5).unwrap(), s.vet_range(3, 8).unwrap()));
+            assert!(!s.eq_ranges(s.vet_range(0, 4).unwrap(), s.vet_range(1, 5).unwrap()));
+
+            // Different lengths.
+            assert!(!s.eq_ranges(first, s.vet_range(3, 5).unwrap()));
+
+            let empty = s.vet_range(2, 2).unwrap();
+            assert!(s.eq_ranges(empty, s.vet_range(7, 7).unwrap()));
+        });
+    }
+
+    #[test]
+    fn replace_take() {
+        let mut v = vec![String::from("a"), String::from("b")];
+
+        region(v.as_mut_slice(), |mut s| {
+            let i = s.vet(1).unwrap();
+
+            assert_eq!(s.replace(i, String::from("c")), "b");
+            assert_eq!(s.take(i), "c");
+            assert_eq!(s[i], "");
+        });
+
+        assert_eq!(v, ["a", ""]);
+    }
+
+    #[test]
+    fn get_disjoint_mut() {
+        let mut v = vec![1, 2, 3, 4];
+
+        region(v.as_mut_slice(), |mut s| {
+            let (a, b, c) = (s.vet(0).unwrap(), s.vet(2).unwrap(), s.vet(3).unwrap());
+
+            let [x, y, z] = s.get_disjoint_mut([c, a, b]).unwrap();
+            *x += *y + *z;
+            *y = 0;
+
+            assert!(s.get_disjoint_mut([a, b, a]).is_none());
+            assert!(s.get_disjoint_mut([b, b]).is_none());
+            assert_eq!(
+                s.get_disjoint_mut([]).map(|refs: [&mut i32; 0]| refs.len()),
+                Some(0)
+            );
+        });
+
+        assert_eq!(v, [0, 2, 3, 8]);
+    }
+
+    #[test]
+    fn index_usize() {
+        let mut v = vec![1, 2, 3];
+
+        region(v.as_mut_slice(), |mut s| {
+            assert_eq!(s[2], 3);
+            s[0] += 10;
+            assert_eq!(s[0], 11);
+        });
+
+        assert_eq!(v, [11, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+    fn index_usize_out_of_bounds() {
+        region(vec![1, 2, 3], |s| s[3]);
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn get_slice() {
+        let mut v = vec![1, 2, 3, 4];
+
+        region(v.as_mut_slice(), |mut s| {
+            assert_eq!(s.get_slice(1..3), Some(&[2, 3][..]));
+            assert_eq!(s.get_slice(4..4), Some(&[][..]));
+            assert_eq!(s.get_slice(3..1), None);
+            assert_eq!(s.get_slice(2..5), None);
+
+            s.get_slice_mut(0..2).unwrap().reverse();
+            assert!(s.get_slice_mut(1..5).is_none());
+
+            assert_eq!(&s[1..4], &[1, 3, 4]);
+            s[2..4].copy_from_slice(&[7, 8]);
+        });
+
+        assert_eq!(v, [2, 1, 7, 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 5 is out of range for a container of length 4")]
+    fn index_range_usize_out_of_bounds() {
+        region(vec![1, 2, 3, 4], |s| s[2..5].len());
+    }
+
+    #[test]
+    fn first_and_tail() {
+        let mut v = vec![1, 2, 3];
+
+        region(v.as_mut_slice(), |mut s| {
+            let last = s.range().nonempty().unwrap().last();
+
+            let (first, tail) = s.first_and_tail().unwrap();
+            assert_eq!(*first, 1);
+            assert_eq!((tail.start(), tail.end()), (1, 3));
+            assert!(tail.contains(last.integer()).is_some());
+            assert_eq!(s[last], 3);
+
+            let (first, tail) = s.first_and_tail_mut().unwrap();
+            *first = 10;
+            for i in tail {
+                s[i] *= 2;
+            }
+        });
+
+        assert_eq!(v, [10, 4, 6]);
+
+        region(Vec::<i32>::new(), |mut s| {
+            assert!(s.first_and_tail().is_none());
+            assert!(s.first_and_tail_mut().is_none());
+        });
+    }
+
+    #[test]
+    fn subcontainer() {
+        use super::{Container, Contract};
+
+        fn sum_recursive<C: for<'s> Contract<'s>>(s: &Container<C, &[i32]>) -> i32 {
+            match s.range().nonempty() {
+                None => 0,
+                Some(r) if r.len() == 1 => s[r.first()],
+                Some(r) => {
+                    let (lhs, rhs) = r.split_in_half();
+                    s.subcontainer(lhs, |c| sum_recursive(&c))
+                        + s.subcontainer(rhs, |c| sum_recursive(&c))
+                }
+            }
+        }
+
+        let mut v = vec![1, 2, 3, 4, 5, 6, 7];
+
+        region(v.as_slice(), |s| assert_eq!(sum_recursive(&s), 28));
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range(2, 5).unwrap();
+            let len = s.subcontainer_mut(r, |mut c| {
+                for i in c.range() {
+                    c[i] = 0;
+                }
+                c.len()
+            });
+            assert_eq!(len, 3);
+        });
+
+        assert_eq!(v, [1, 2, 0, 0, 0, 6, 7]);
+    }
+
+    #[test]
+    fn zip_with() {
+        let mut y = vec![1.0, 2.0, 3.0];
+        let x = vec![10.0, 20.0, 30.0];
+
+        region(y.as_mut_slice(), |mut y| {
+            region(x.as_slice(), |x| {
+                let proof = y.len_eq(&x).unwrap();
+                y.zip_with(&x, proof, |y, x| *y += 2.0 * x);
+            })
+        });
+
+        assert_eq!(y, [21.0, 42.0, 63.0]);
+    }
+
+    #[test]
+    fn zip_range_with() {
+        use crate::container::error::LengthMismatch;
+
+        let mut a = vec![1, 2, 3, 4];
+        let b = vec![10, 20, 30];
+
+        region(a.as_mut_slice(), |mut a| {
+            region(b.as_slice(), |b| {
+                let dst = a.vet_range(1, 3).unwrap();
+                let src = b.vet_range(0, 2).unwrap();
+                assert!(a.zip_range_with(dst, &b, src, |x, y| *x *= y).is_ok());
+
+                let err = a.zip_range_with(a.range(), &b, src, |_, _| panic!("called"));
+                assert_eq!(err, Err(LengthMismatch::new(4, 2)));
+            })
+        });
+
+        assert_eq!(a, [1, 20, 60, 4]);
+    }
+
+    #[test]
+    fn prefix_suffix() {
+        let v = b"HDRpayloadTR".to_vec();
+
+        region(v.as_slice(), |s| {
+            let header = s.prefix(3).unwrap();
+            let trailer = s.suffix(2).unwrap();
+            assert_eq!(&s[header], b"HDR");
+            assert_eq!(&s[trailer], b"TR");
+
+            assert!(s.prefix(12).unwrap() == s.range());
+            assert!(s.suffix(12).unwrap() == s.range());
+
+            assert!(s.prefix(0).is_none());
+            assert!(s.suffix(0).is_none());
+            assert!(s.prefix(13).is_none());
+            assert!(s.suffix(13).is_none());
+        });
+    }
+
+    #[test]
+    fn ptr_range() {
+        extern "C" fn c_sum(ptr: *const u32, len: usize) -> u32 {
+            unsafe { core::slice::from_raw_parts(ptr, len).iter().sum() }
+        }
+
+        extern "C" fn c_fill(ptr: *mut u32, len: usize, value: u32) {
+            for i in 0..len {
+                unsafe { ptr.add(i).write(value) }
+            }
+        }
+
+        let mut v = vec![1u32, 2, 3, 4, 5];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range(1, 4).unwrap();
+
+            let p = s.ptr_range(r);
+            assert_eq!(p.end as usize - p.start as usize, 3 * 4);
+            assert_eq!(c_sum(p.start, r.len()), 9);
+
+            let p = s.mut_ptr_range(r);
+            c_fill(p.start, r.len(), 7);
+
+            let empty = s.vet_range(5, 5).unwrap();
+            let p = s.ptr_range(empty);
+            assert_eq!(p.start, p.end);
+            assert_eq!(c_sum(p.start, empty.len()), 0);
+        });
+
+        assert_eq!(v, [1, 7, 7, 7, 5]);
+    }
+
+    #[test]
+    fn compact() {
+        let mut v = vec![1, 2, 3, 4, 5, 6, 7, 8];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range(1, 7).unwrap();
+            let kept = s.compact(r, |&x| x % 2 == 0);
+            assert_eq!((kept.start(), kept.end()), (1, 4));
+            assert_eq!(s[kept], [2, 4, 6]);
+
+            let mut rest = s[s.vet_range(4, 7).unwrap()].to_vec();
+            rest.sort_unstable();
+            assert_eq!(rest, [3, 5, 7]);
+
+            let none = s.compact(s.range(), |_| false);
+            assert!(none.is_empty());
+            assert_eq!(none.start(), 0);
+        });
+
+        assert_eq!((v[0], v[7]), (1, 8));
+
+        let mut words: Vec<String> = ["a", "bb", "c", "dd", "ee"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+
+        region(words.as_mut_slice(), |mut s| {
+            let all = s.compact(s.range(), |_| true);
+            assert!(all == s.range());
+
+            let kept = s.compact(s.range(), |w| w.len() == 2);
+            assert_eq!(s[kept], ["bb", "dd", "ee"]);
+        });
+
+        words.sort();
+        assert_eq!(words, ["a", "bb", "c", "dd", "ee"]);
+    }
+
+    #[test]
+    fn dedup_range() {
+        let mut v = vec![1, 1, 2, 3, 3, 3, 4, 1];
+
+        region(v.as_mut_slice(), |mut s| {
+            let unique = s.dedup_range(s.range());
+            assert_eq!(s[unique], [1, 2, 3, 4, 1]);
+        });
+
+        let mut same = vec![7; 5];
+        region(same.as_mut_slice(), |mut s| {
+            let unique = s.dedup_range(s.range());
+            assert_eq!(s[unique], [7]);
+        });
+
+        let mut distinct = vec![1, 2, 3];
+        region(distinct.as_mut_slice(), |mut s| {
+            let unique = s.dedup_range(s.range());
+            assert!(unique == s.range());
+
+            let empty = s.vet_range(2, 2).unwrap();
+            assert!(s.dedup_range(empty) == empty);
+        });
+        assert_eq!(distinct, [1, 2, 3]);
+
+        let mut words = vec!["a", "A", "b", "B", "b", "c"];
+        region(words.as_mut_slice(), |mut s| {
+            let r = s.vet_range(1, 6).unwrap();
+            let unique = s.dedup_range_by(r, |a, b| a.eq_ignore_ascii_case(b));
+            assert_eq!((unique.start(), unique.end()), (1, 4));
+            assert_eq!(s[unique], ["A", "b", "c"]);
+        });
+    }
+
+    #[test]
+    fn dedup_range_drop() {
+        use std::cell::Cell;
+
+        struct Tracked<'a>(u8, &'a Cell<usize>);
+
+        impl Drop for Tracked<'_> {
+            fn drop(&mut self) {
+                self.1.set(self.1.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        let mut v: Vec<_> = [1, 1, 2, 2, 2, 3]
+            .iter()
+            .map(|&n| Tracked(n, &drops))
+            .collect();
+
+        region(v.as_mut_slice(), |mut s| {
+            let unique = s.dedup_range_by(s.range(), |a, b| a.0 == b.0);
+            let keys: Vec<u8> = s[unique].iter().map(|t| t.0).collect();
+            assert_eq!(keys, [1, 2, 3]);
+        });
+
+        assert_eq!(drops.get(), 0);
+        drop(v);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn shift_right_left() {
+        let mut v = vec![1, 2, 3, 4, 5, 6];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range_nonempty(0, 3).unwrap();
+            assert!(s.shift_right(r, 0).unwrap() == r);
+            assert_eq!(s[s.range()], [1, 2, 3, 4, 5, 6]);
+
+            // Overlapping source and destination.
+            let dst = s.shift_right(r, 2).unwrap();
+            assert_eq!((dst.start(), dst.end()), (2, 5));
+            assert_eq!(s[s.range()], [1, 2, 1, 2, 3, 6]);
+
+            let dst = s.shift_left(dst, 1).unwrap();
+            assert_eq!(s[dst], [1, 2, 3]);
+            assert_eq!(s[s.range()], [1, 1, 2, 3, 3, 6]);
+
+            assert!(s.shift_right(r, 4).is_none());
+            assert!(s.shift_right(r, usize::MAX).is_none());
+            assert!(s.shift_left(dst, 2).is_none());
+            assert_eq!(s[s.range()], [1, 1, 2, 3, 3, 6]);
+
+            let last = s.vet_range_nonempty(5, 6).unwrap();
+            assert!(s.shift_left(last, 5).is_some());
+            assert_eq!(s[s.range()], [6, 1, 2, 3, 3, 6]);
+        });
+    }
+
+    #[test]
+    fn shift_cloned() {
+        let mut v: Vec<String> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range_nonempty(0, 3).unwrap();
+            let dst = s.shift_right_cloned(r, 1).unwrap();
+            assert_eq!(s[dst], ["a", "b", "c"]);
+            assert_eq!(s[s.range()], ["a", "a", "b", "c", "e"]);
+
+            let dst = s.shift_left_cloned(dst, 1).unwrap();
+            assert_eq!(s[dst], ["a", "b", "c"]);
+            assert_eq!(s[s.range()], ["a", "b", "c", "c", "e"]);
+
+            assert!(s.shift_right_cloned(r, 3).is_none());
+            assert!(s.shift_left_cloned(r, 1).is_none());
+            assert!(s.shift_left_cloned(r, 0).unwrap() == r);
+        });
+    }
+
+    #[test]
+    fn apply() {
+        let mut v = vec![1, 2, 3, 4];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range(1, 3).unwrap();
+            s.apply(r, |x| *x *= 10);
+
+            let last = s.range().nonempty().unwrap().last();
+            s.apply_indexed(s.range(), |i, x| {
+                if i == last {
+                    *x = 0;
+                } else {
+                    *x += i.integer() as i32;
+                }
+            });
+        });
+
+        assert_eq!(v, [1, 21, 32, 0]);
+    }
+
+    #[test]
+    fn fold() {
+        let v = vec![1u8, 2, 3, 200, 100];
+
+        region(v.as_slice(), |s| {
+            let r = s.vet_range(0, 3).unwrap();
+            assert_eq!(s.fold(r, 0u32, |acc, &x| acc + u32::from(x)), 6);
+            assert_eq!(s.fold(s.vet_range(2, 2).unwrap(), 7, |acc, _| acc + 1), 7);
+
+            let mut calls = 0;
+            let sum = s.try_fold(s.range(), 0u8, |acc, &x| {
+                calls += 1;
+                acc.checked_add(x).ok_or(acc)
+            });
+            assert_eq!(sum, Err(206));
+            assert_eq!(calls, 5);
+
+            assert_eq!(
+                s.try_fold(r, 0u8, |acc, &x| acc.checked_add(x).ok_or(acc)),
+                Ok(6)
+            );
+        });
+    }
+
+    #[test]
+    fn count() {
+        let text = b"the quick brown fox jumps over the lazy dog".to_vec();
+
+        region(text.as_slice(), |s| {
+            assert_eq!(s.count(s.range(), |&b| b == b' '), 8);
+            assert_eq!(s.count_byte(s.range(), b' '), 8);
+            assert_eq!(s.count_byte(s.range(), b'o'), 4);
+
+            let first = s.vet_range(0, 9).unwrap();
+            assert_eq!(s.count_byte(first, b'e'), 1);
+            assert_eq!(s.count(first, u8::is_ascii_lowercase), 8);
+            assert_eq!(s.count_byte(s.vet_range(3, 3).unwrap(), b' '), 0);
+        });
+    }
+
+    #[test]
+    fn copy_to_slice() {
+        use crate::container::error::LengthMismatch;
+        use core::mem::MaybeUninit;
+
+        let v = vec![1, 2, 3, 4];
+        let words = vec![String::from("a"), String::from("b")];
+
+        region(v.as_slice(), |s| {
+            let r = s.vet_range(1, 3).unwrap();
+
+            let mut out = [0; 2];
+            assert_eq!(s.copy_to_slice(r, &mut out), Ok(()));
+            assert_eq!(out, [2, 3]);
+
+            let mut short = [0; 1];
+            assert_eq!(
+                s.copy_to_slice(r, &mut short),
+                Err(LengthMismatch::new(2, 1))
+            );
+            assert_eq!(short, [0]);
+
+            let mut uninit = [MaybeUninit::uninit(); 4];
+            assert_eq!(
+                s.copy_to_uninit(s.range(), &mut uninit),
+                Ok(&mut [1, 2, 3, 4][..])
+            );
+            assert!(s.copy_to_uninit(r, &mut uninit).is_err());
+        });
+
+        region(words.as_slice(), |s| {
+            let mut out = vec![String::new(); 2];
+            assert_eq!(s.clone_to_slice(s.range(), &mut out), Ok(()));
+            assert_eq!(out, ["a", "b"]);
+            assert!(s.clone_to_slice(s.range(), &mut out[..1]).is_err());
+        });
+    }
+
+    #[test]
+    fn indices_where() {
+        let mut v = vec![3, 8, 1, 6, 7, 4, 9];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.vet_range(1, 6).unwrap();
+
+            let mut iter = s.indices_where(r, |&x| x % 2 == 0);
+            assert_eq!(iter.size_hint(), (0, Some(5)));
+
+            let evens: Vec<_> = iter.by_ref().collect();
+            assert!(iter.next().is_none());
+            assert_eq!(iter.size_hint(), (0, Some(0)));
+
+            for i in evens {
+                s[i] = 0;
+            }
+        });
+
+        assert_eq!(v, [3, 0, 1, 0, 7, 0, 9]);
+    }
+
+    #[test]
+    fn split_at_with_ranges() {
+        use super::{Container, Contract, NonEmpty, Range};
+
+        fn sum_all<C: for<'s> Contract<'s>>(
+            mut s: Container<C, &mut [u32]>,
+            r: Range<C, NonEmpty>,
+        ) -> u32 {
+            let mid = r.upper_middle();
+
+            if mid == r.first() {
+                return s[mid];
+            }
+
+            s.split_at_mut_with_ranges(mid, |(lhs, lhs_range), (rhs, rhs_range), _| {
+                lhs_range.map_or(0, |lr| sum_all(lhs, lr)) + sum_all(rhs, rhs_range)
+            })
+        }
+
+        let mut v = vec![1, 2, 3, 4, 5];
+        region(v.as_mut_slice(), |s| {
+            let r = s.range().nonempty().unwrap();
+            assert_eq!(sum_all(s, r), 15);
+        });
+
+        region(v.as_slice(), |s| {
+            s.split_at_with_ranges(s.start_edge(), |(lhs, lhs_range), (rhs, rhs_range), _| {
+                assert!(lhs.is_empty() && lhs_range.is_none());
+                assert_eq!(rhs[rhs_range], [1, 2, 3, 4, 5]);
+            });
+
+            let mid = s.vet(2).unwrap();
+            s.split_at_with_ranges(mid, |(lhs, lhs_range), (rhs, rhs_range), _| {
+                assert_eq!(lhs[lhs_range.unwrap()], [1, 2]);
+                assert_eq!(rhs[rhs_range.first()], 3);
+            });
+        });
+    }
+
+    #[test]
+    fn first_last_index() {
+        region(vec![4, 5, 6], |s| {
+            assert_eq!(s[s.first_index().unwrap()], 4);
+            assert_eq!(s[s.last_index().unwrap()], 6);
+            assert_eq!(s.last_index().map(|i| i.integer()), Some(2));
+        });
+
+        region(vec![7], |s| {
+            assert!(s.first_index().unwrap() == s.last_index().unwrap());
+        });
+
+        region(Vec::<i32>::new(), |s| {
+            assert!(s.first_index().is_none());
+            assert!(s.last_index().is_none());
+        });
+    }
+
+    #[test]
+    fn windows_branded() {
+        let v = vec![1, 2, 3, 4, 5];
+        let three = NonZeroUsize::new(3).unwrap();
+
+        region(v.as_slice(), |s| {
+            let mut windows = s.windows_branded(three);
+            assert_eq!(windows.len(), 3);
+
+            let first = windows.next(|w| w[w.range()].to_vec());
+            assert_eq!(first, Some(vec![1, 2, 3]));
+
+            let mut sums = Vec::new();
+            windows.for_each(|w| {
+                let last = w.last_index().unwrap();
+                sums.push(w.fold(w.range(), 0, |acc, x| acc + x));
+                assert_eq!(last.integer(), 2);
+            });
+            assert_eq!(sums, [9, 12]);
+
+            let mut windows = s.windows_branded(NonZeroUsize::new(6).unwrap());
+            assert!(windows.is_empty());
+            assert_eq!(windows.next(|w| w.len()), None);
+
+            let mut windows = s.windows_branded(NonZeroUsize::new(5).unwrap());
+            assert_eq!(windows.next(|w| w.len()), Some(5));
+            assert!(windows.is_empty());
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "paranoid")]
+    #[should_panic(expected = "index 3 is out of bounds of a container of length 3")]
+    fn paranoid_index_out_of_bounds() {
+        region(vec![1, 2, 3], |s| {
+            let _ = s[unsafe { crate::core::index::Index::new(3) }];
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "paranoid")]
+    #[should_panic(expected = "edge 4 is out of bounds of a container of length 3")]
+    fn paranoid_range_out_of_bounds() {
+        region(vec![1, 2, 3], |s| {
+            let _ = &s[unsafe { crate::core::range::Range::from_unknown(0, 4) }];
+        });
+    }
+
+    /// A slice of the maximum number of zero-sized elements.
+    fn max_len_zst() -> &'static [()] {
+        unsafe { core::slice::from_raw_parts(core::ptr::NonNull::dangling().as_ptr(), usize::MAX) }
+    }
+
+    #[test]
+    fn scan_from_at_max_len() {
+        region(max_len_zst(), |s| {
+            let last = s.range().nonempty().unwrap().last();
+            assert_eq!(last.integer(), usize::MAX - 1);
+
+            let r = s.scan_from(last, |_| true);
+            assert_eq!((r.start(), r.end()), (usize::MAX - 1, usize::MAX));
+            assert_eq!(s[r].len(), 1);
+
+            let r = s.scan_from_rev(last, |_| false);
+            assert_eq!((r.start(), r.end()), (usize::MAX - 1, usize::MAX));
+
+            let tail = s.vet_range(usize::MAX - 3, usize::MAX).unwrap();
+            assert_eq!(s[tail.first()..].len(), 3);
+            assert_eq!(tail.pairs().count(), 2);
+            assert_eq!(tail.into_iter().last().unwrap().integer(), usize::MAX - 1);
+        });
+    }
+
+    #[test]
+    fn advance_at_max_len() {
+        region(max_len_zst(), |s| {
+            let mut r = s.vet_range_nonempty(usize::MAX - 2, usize::MAX).unwrap();
+
+            assert!(r.advance());
+            assert_eq!(r.first().integer(), usize::MAX - 1);
+            assert!(!r.advance());
+            assert_eq!(r.last().integer(), usize::MAX - 1);
+            assert!(r.tail().is_empty());
+
+            let edge = s.vet_edge(usize::MAX).unwrap();
+            assert_eq!(s[..edge].len(), usize::MAX);
+        });
+    }
+
+    #[test]
+    fn windows_at_max_len() {
+        region(max_len_zst(), |s| {
+            let all = NonZeroUsize::new(usize::MAX).unwrap();
+            assert_eq!(s.windows(all).len(), 1);
+
+            let two = NonZeroUsize::new(2).unwrap();
+            let last = s.windows(two).next_back().unwrap();
+            assert_eq!((last.start(), last.end()), (usize::MAX - 2, usize::MAX));
+        });
+    }
+
+    #[test]
+    fn interleaved_range_access() {
+        let mut v = vec![1, 2, 3, 4, 5, 6];
+
+        region(v.as_mut_slice(), |mut s| {
+            let a = s.vet_range_nonempty(0, 3).unwrap();
+            let b = s.vet_range_nonempty(3, 6).unwrap();
+
+            let before = s[a].as_ptr();
+            s[b][0] = 40;
+            s[a][2] = 30;
+            assert_eq!(s[a].as_ptr(), before);
+            assert_eq!(s[a.first()..], [1, 2, 30, 40, 5, 6]);
+
+            s.swap_ranges(a, b);
+            s[..b.first()][0] += 100;
+            assert_eq!(s[b], [1, 2, 30]);
+
+            s.copy_within(b, a.first());
+            *s.get_pair_mut(a.first(), b.last()).unwrap().1 += 1;
+            assert_eq!(s[..], [1, 2, 30, 1, 2, 31]);
+        });
+    }
+}
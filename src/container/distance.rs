@@ -0,0 +1,29 @@
+/// Elements with a distance between them, used to find the element nearest to a value.
+///
+/// The distance must be symmetric, and may not overflow:
+/// implementations for signed integers return the unsigned difference.
+pub trait Distance {
+    /// The type of the distance, which is ordered from near to far.
+    type Output: Ord;
+
+    /// Returns the distance between `self` and `other`.
+    fn distance(&self, other: &Self) -> Self::Output;
+}
+
+macro_rules! distance {
+    ($($ty:ty => $out:ty),*) => {$(
+        impl Distance for $ty {
+            type Output = $out;
+
+            #[inline(always)]
+            fn distance(&self, other: &Self) -> $out {
+                self.abs_diff(*other)
+            }
+        }
+    )*};
+}
+
+distance!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize
+);
@@ -0,0 +1,110 @@
+use core::fmt;
+
+/// The error returned when two ranges were required to be of equal length,
+/// but were not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LengthMismatch {
+    left: usize,
+    right: usize,
+}
+
+impl LengthMismatch {
+    #[inline(always)]
+    pub(crate) fn new(left: usize, right: usize) -> Self {
+        Self { left, right }
+    }
+
+    /// Returns the length of the left-hand side.
+    #[inline(always)]
+    pub fn left(&self) -> usize {
+        self.left
+    }
+
+    /// Returns the length of the right-hand side.
+    #[inline(always)]
+    pub fn right(&self) -> usize {
+        self.right
+    }
+}
+
+impl fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "length mismatch: the left-hand side has length {}, but the right-hand side has length {}",
+            self.left, self.right
+        )
+    }
+}
+
+impl std::error::Error for LengthMismatch {}
+
+/// The error returned when an untrusted index is not within the bounds of a container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OutOfBounds {
+    index: usize,
+    len: usize,
+}
+
+impl OutOfBounds {
+    #[inline(always)]
+    pub(crate) fn new(index: usize, len: usize) -> Self {
+        Self { index, len }
+    }
+
+    /// Returns the offending index.
+    #[inline(always)]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the length of the container.
+    #[inline(always)]
+    pub fn length(&self) -> usize {
+        self.len
+    }
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "index out of bounds: the len is {} but the index is {}",
+            self.len, self.index
+        )
+    }
+}
+
+impl std::error::Error for OutOfBounds {}
+
+/// The error returned when an untrusted pair of `start` and `end` does not make up a valid range
+/// within a container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RangeError {
+    /// The start of the range lies after its end.
+    Inverted { start: usize, end: usize },
+
+    /// The end of the range lies past the length of the container.
+    EndOutOfBounds { end: usize, len: usize },
+
+    /// The range was required to be non-empty, but is empty.
+    Empty { at: usize },
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RangeError::Inverted { start, end } => {
+                write!(f, "range start index {} is after end index {}", start, end)
+            }
+            RangeError::EndOutOfBounds { end, len } => write!(
+                f,
+                "range end index {} is out of range for a container of length {}",
+                end, len
+            ),
+            RangeError::Empty { at } => write!(f, "range at index {} is empty", at),
+        }
+    }
+}
+
+impl std::error::Error for RangeError {}
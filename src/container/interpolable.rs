@@ -0,0 +1,22 @@
+/// Elements that can be mapped onto a numeric scale,
+/// such that the position of a value within a sorted container can be estimated by interpolation.
+///
+/// The mapping must be monotonic: if `a <= b`, then `a.interpolation_key() <= b.interpolation_key()`.
+/// It does not need to be exact, a lossy mapping only makes the estimates worse.
+pub trait Interpolable {
+    /// Returns the position of `self` on the numeric scale.
+    fn interpolation_key(&self) -> f64;
+}
+
+macro_rules! interpolable {
+    ($($ty:ty),*) => {$(
+        impl Interpolable for $ty {
+            #[inline(always)]
+            fn interpolation_key(&self) -> f64 {
+                *self as f64
+            }
+        }
+    )*};
+}
+
+interpolable!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
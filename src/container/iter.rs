@@ -0,0 +1,437 @@
+use core::{cmp, iter, num::NonZeroUsize, slice};
+
+use crate::{
+    container::{
+        traits::{ContainerTrait, GetUnchecked},
+        Container,
+    },
+    core::{
+        index::Index,
+        proof::NonEmpty,
+        range::{Range, RangeIter},
+        seal::{Contract, Seal, Signed},
+    },
+};
+
+/// An iterator over the maximal runs of adjacent elements that are considered equal.
+///
+/// This struct is created by [`Container::group_by`].
+pub struct GroupBy<'a, C: for<'s> Contract<'s>, A, F> {
+    container: &'a Container<C, A>,
+    rest: Range<C>,
+    eq: F,
+}
+
+impl<'a, C: for<'s> Contract<'s>, A: ContainerTrait, F> GroupBy<'a, C, A, F> {
+    #[inline(always)]
+    pub(crate) fn new(container: &'a Container<C, A>, eq: F) -> Self {
+        Self {
+            container,
+            rest: container.range(),
+            eq,
+        }
+    }
+}
+
+impl<'a, C, A, T, F> Iterator for GroupBy<'a, C, A, F>
+where
+    C: for<'s> Contract<'s>,
+    A: GetUnchecked<Item = T>,
+    F: FnMut(&T, &T) -> bool,
+{
+    type Item = Range<C, NonEmpty>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.rest.nonempty()?;
+        let (container, eq) = (self.container, &mut self.eq);
+
+        let end = rest
+            .pairs()
+            .find(|&(a, b)| !eq(&container[a], &container[b]))
+            .map_or(rest.end(), |(_, b)| b.integer());
+
+        unsafe {
+            self.rest = Range::from_unknown(end, rest.end());
+
+            Some(Range::from_nonempty(rest.start(), end))
+        }
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.rest.len();
+        (usize::from(len != 0), Some(len))
+    }
+}
+
+/// An iterator over the non-empty ranges in between separator elements.
+///
+/// This type is returned by [`Container::split_by_nonempty`].
+pub type SplitByNonEmpty<'a, C, A, F> =
+    iter::FilterMap<SplitBy<'a, C, A, F>, fn(Range<C>) -> Option<Range<C, NonEmpty>>>;
+
+/// An iterator over the ranges in between separator elements.
+///
+/// This struct is created by [`Container::split_by`].
+pub struct SplitBy<'a, C: for<'s> Contract<'s>, A, F> {
+    container: &'a Container<C, A>,
+    rest: Range<C>,
+    is_sep: F,
+    finished: bool,
+}
+
+impl<'a, C: for<'s> Contract<'s>, A: ContainerTrait, F> SplitBy<'a, C, A, F> {
+    #[inline(always)]
+    pub(crate) fn new(container: &'a Container<C, A>, is_sep: F) -> Self {
+        Self {
+            container,
+            rest: container.range(),
+            is_sep,
+            finished: false,
+        }
+    }
+}
+
+impl<'a, C, A, T, F> Iterator for SplitBy<'a, C, A, F>
+where
+    C: for<'s> Contract<'s>,
+    A: GetUnchecked<Item = T>,
+    F: FnMut(&T) -> bool,
+{
+    type Item = Range<C>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let (container, is_sep) = (self.container, &mut self.is_sep);
+        let rest = self.rest;
+
+        match rest.into_iter().find(|&index| is_sep(&container[index])) {
+            Some(sep) => unsafe {
+                self.rest = Range::from_unknown(sep.integer() + 1, rest.end());
+
+                Some(Range::from_unknown(rest.start(), sep.integer()))
+            },
+            None => {
+                self.finished = true;
+
+                Some(rest)
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            (0, Some(0))
+        } else {
+            (1, Some(self.rest.len() + 1))
+        }
+    }
+}
+
+/// An iterator over the indices of the elements that match a predicate.
+///
+/// This struct is created by [`Container::indices_where`].
+pub struct IndicesWhere<'a, C: for<'s> Contract<'s>, A, F> {
+    container: &'a Container<C, A>,
+    rest: RangeIter<C>,
+    pred: F,
+}
+
+impl<'a, C: for<'s> Contract<'s>, A, F> IndicesWhere<'a, C, A, F> {
+    #[inline(always)]
+    pub(crate) fn new<P>(container: &'a Container<C, A>, r: Range<C, P>, pred: F) -> Self {
+        Self {
+            container,
+            rest: r.into_iter(),
+            pred,
+        }
+    }
+}
+
+impl<'a, C, A, T, F> Iterator for IndicesWhere<'a, C, A, F>
+where
+    C: for<'s> Contract<'s>,
+    A: GetUnchecked<Item = T>,
+    F: FnMut(&T) -> bool,
+{
+    type Item = Index<C>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (container, pred) = (self.container, &mut self.pred);
+
+        self.rest.find(|&index| pred(&container[index]))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.rest.size_hint().1)
+    }
+}
+
+impl<'a, C, A, T, F> iter::FusedIterator for IndicesWhere<'a, C, A, F>
+where
+    C: for<'s> Contract<'s>,
+    A: GetUnchecked<Item = T>,
+    F: FnMut(&T) -> bool,
+{
+}
+
+/// An iterator over all overlapping windows of a fixed length.
+///
+/// This struct is created by [`Container::windows`].
+#[allow(unused)]
+pub struct Windows<C: for<'s> Contract<'s>> {
+    front: usize,
+    back: usize,
+    size: NonZeroUsize,
+    contract: Seal<C>,
+}
+
+impl<C: for<'s> Contract<'s>> Windows<C> {
+    /// Creates an iterator over the windows of length `size`, within a container of length `len`.
+    #[inline(always)]
+    pub(crate) fn new(len: usize, size: NonZeroUsize) -> Self {
+        Self {
+            front: 0,
+            back: len.saturating_sub(size.get() - 1),
+            size,
+            contract: Seal::new(),
+        }
+    }
+}
+
+impl<C: for<'s> Contract<'s>> Iterator for Windows<C> {
+    type Item = Range<C, NonEmpty>;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            let start = self.front;
+            self.front += 1;
+
+            unsafe { Some(Range::from_nonempty(start, start + self.size.get())) }
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<C: for<'s> Contract<'s>> DoubleEndedIterator for Windows<C> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.back -= 1;
+            let start = self.back;
+
+            unsafe { Some(Range::from_nonempty(start, start + self.size.get())) }
+        } else {
+            None
+        }
+    }
+}
+
+impl<C: for<'s> Contract<'s>> ExactSizeIterator for Windows<C> {}
+
+/// A lending iterator over all overlapping windows of a fixed length,
+/// each lent out as a container with a contract of its own.
+///
+/// Every window is only available within the closure passed to [`BrandedWindows::next`],
+/// so indices of one window can not be used with another.
+///
+/// This struct is created by [`Container::windows_branded`].
+pub struct BrandedWindows<'a, T> {
+    windows: slice::Windows<'a, T>,
+}
+
+impl<'a, T> BrandedWindows<'a, T> {
+    #[inline(always)]
+    pub(crate) fn new(slice: &'a [T], size: NonZeroUsize) -> Self {
+        Self {
+            windows: slice.windows(size.get()),
+        }
+    }
+
+    /// Calls `f` with the next window, and returns its output,
+    /// or returns None if all windows have been lent out.
+    #[inline]
+    pub fn next<F, Out>(&mut self, f: F) -> Option<Out>
+    where
+        F: for<'id> FnOnce(Container<Signed<'id>, &'a [T]>) -> Out,
+    {
+        self.windows.next().map(|window| f(Container::new(window)))
+    }
+
+    /// Calls `f` with every remaining window, in order.
+    #[inline]
+    pub fn for_each<F>(mut self, mut f: F)
+    where
+        F: for<'id> FnMut(Container<Signed<'id>, &'a [T]>),
+    {
+        while self.next(&mut f).is_some() {}
+    }
+
+    /// Returns the number of windows that have not been lent out yet.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    /// Returns `true` if all windows have been lent out.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Returns the length of a chunk at the anchored end of `len` elements, chunked by `size`.
+#[inline(always)]
+fn anchored_len(len: usize, size: NonZeroUsize) -> usize {
+    cmp::min(len, size.get())
+}
+
+/// Returns the length of the chunk at the end opposite of the anchor of `len` elements, chunked by `size`.
+/// This is the only chunk that may be shorter than `size`.
+#[inline(always)]
+fn remainder_len(len: usize, size: NonZeroUsize) -> usize {
+    match len % size {
+        0 => anchored_len(len, size),
+        remainder => remainder,
+    }
+}
+
+/// An iterator over consecutive, non-overlapping chunks, starting at the front.
+///
+/// This struct is created by [`Container::chunks`].
+#[allow(unused)]
+pub struct Chunks<C: for<'s> Contract<'s>> {
+    start: usize,
+    end: usize,
+    size: NonZeroUsize,
+    contract: Seal<C>,
+}
+
+impl<C: for<'s> Contract<'s>> Chunks<C> {
+    /// Creates an iterator over the chunks of length `size`, within a container of length `len`.
+    #[inline(always)]
+    pub(crate) fn new(len: usize, size: NonZeroUsize) -> Self {
+        Self {
+            start: 0,
+            end: len,
+            size,
+            contract: Seal::new(),
+        }
+    }
+}
+
+impl<C: for<'s> Contract<'s>> Iterator for Chunks<C> {
+    type Item = Range<C, NonEmpty>;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start < self.end {
+            let start = self.start;
+            self.start += anchored_len(self.end - self.start, self.size);
+
+            unsafe { Some(Range::from_nonempty(start, self.start)) }
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end - self.start).div_ceil(self.size.get());
+        (len, Some(len))
+    }
+}
+
+impl<C: for<'s> Contract<'s>> DoubleEndedIterator for Chunks<C> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start < self.end {
+            let end = self.end;
+            self.end -= remainder_len(self.end - self.start, self.size);
+
+            unsafe { Some(Range::from_nonempty(self.end, end)) }
+        } else {
+            None
+        }
+    }
+}
+
+impl<C: for<'s> Contract<'s>> ExactSizeIterator for Chunks<C> {}
+
+/// An iterator over consecutive, non-overlapping chunks, starting at the back.
+///
+/// This struct is created by [`Container::rchunks`].
+#[allow(unused)]
+pub struct RChunks<C: for<'s> Contract<'s>> {
+    start: usize,
+    end: usize,
+    size: NonZeroUsize,
+    contract: Seal<C>,
+}
+
+impl<C: for<'s> Contract<'s>> RChunks<C> {
+    /// Creates an iterator over the chunks of length `size`, within a container of length `len`.
+    #[inline(always)]
+    pub(crate) fn new(len: usize, size: NonZeroUsize) -> Self {
+        Self {
+            start: 0,
+            end: len,
+            size,
+            contract: Seal::new(),
+        }
+    }
+}
+
+impl<C: for<'s> Contract<'s>> Iterator for RChunks<C> {
+    type Item = Range<C, NonEmpty>;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start < self.end {
+            let end = self.end;
+            self.end -= anchored_len(self.end - self.start, self.size);
+
+            unsafe { Some(Range::from_nonempty(self.end, end)) }
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end - self.start).div_ceil(self.size.get());
+        (len, Some(len))
+    }
+}
+
+impl<C: for<'s> Contract<'s>> DoubleEndedIterator for RChunks<C> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start < self.end {
+            let start = self.start;
+            self.start += remainder_len(self.end - self.start, self.size);
+
+            unsafe { Some(Range::from_nonempty(start, self.start)) }
+        } else {
+            None
+        }
+    }
+}
+
+impl<C: for<'s> Contract<'s>> ExactSizeIterator for RChunks<C> {}
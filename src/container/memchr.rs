@@ -0,0 +1,145 @@
+//! Word-at-a-time byte search, used by [`Container::index_of`](super::Container::index_of),
+//! [`Container::find_byte`](super::Container::find_byte)
+//! and [`Container::count_byte`](super::Container::count_byte).
+
+use core::mem;
+
+const LO: usize = usize::from_ne_bytes([0x01; mem::size_of::<usize>()]);
+const HI: usize = usize::from_ne_bytes([0x80; mem::size_of::<usize>()]);
+
+/// Returns `true` if any byte within `word` is zero.
+#[inline(always)]
+fn contains_zero_byte(word: usize) -> bool {
+    word.wrapping_sub(LO) & !word & HI != 0
+}
+
+/// Returns the offset of the first byte within `haystack` that equals `needle`.
+#[inline]
+pub(crate) fn memchr(needle: u8, haystack: &[u8]) -> Option<usize> {
+    let (prefix, words, _) = unsafe { haystack.align_to::<usize>() };
+
+    if let Some(offset) = prefix.iter().position(|&b| b == needle) {
+        return Some(offset);
+    }
+
+    let repeated = usize::from_ne_bytes([needle; mem::size_of::<usize>()]);
+
+    // Skip over every word that does not contain `needle`,
+    // the remaining bytes are searched one by one.
+    let skipped = words
+        .iter()
+        .position(|&word| contains_zero_byte(word ^ repeated))
+        .unwrap_or(words.len());
+
+    let start = prefix.len() + skipped * mem::size_of::<usize>();
+
+    haystack[start..]
+        .iter()
+        .position(|&b| b == needle)
+        .map(|offset| start + offset)
+}
+
+/// Returns the offset of the last byte within `haystack` that equals `needle`.
+#[inline]
+pub(crate) fn memrchr(needle: u8, haystack: &[u8]) -> Option<usize> {
+    let (prefix, words, suffix) = unsafe { haystack.align_to::<usize>() };
+    let suffix_start = haystack.len() - suffix.len();
+
+    if let Some(offset) = suffix.iter().rposition(|&b| b == needle) {
+        return Some(suffix_start + offset);
+    }
+
+    let repeated = usize::from_ne_bytes([needle; mem::size_of::<usize>()]);
+
+    // Skip over every word from the back that does not contain `needle`,
+    // the remaining bytes are searched one by one.
+    let kept = words
+        .iter()
+        .rposition(|&word| contains_zero_byte(word ^ repeated))
+        .map_or(0, |index| index + 1);
+
+    let end = prefix.len() + kept * mem::size_of::<usize>();
+
+    haystack[..end].iter().rposition(|&b| b == needle)
+}
+
+/// Returns the number of bytes within `haystack` that equal `needle`.
+#[inline]
+pub(crate) fn count(needle: u8, haystack: &[u8]) -> usize {
+    let (prefix, words, suffix) = unsafe { haystack.align_to::<usize>() };
+
+    let bytewise = |bytes: &[u8]| bytes.iter().filter(|&&b| b == needle).count();
+    let repeated = usize::from_ne_bytes([needle; mem::size_of::<usize>()]);
+
+    let counted: usize = words
+        .iter()
+        .map(|&word| count_zero_bytes(word ^ repeated))
+        .sum();
+
+    bytewise(prefix) + counted + bytewise(suffix)
+}
+
+/// Returns the number of bytes within `word` that are zero.
+#[inline(always)]
+fn count_zero_bytes(word: usize) -> usize {
+    const LOW: usize = !HI;
+
+    // The high bit of every byte is set, unless the byte is zero.
+    // The addition can not carry into the next byte.
+    let nonzero = ((word & LOW) + LOW) | word | LOW;
+
+    (!nonzero).count_ones() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{count, memchr, memrchr};
+
+    #[test]
+    fn memchr_matches_position() {
+        let haystack = (0..100u8).map(|b| b % 37).collect::<Vec<_>>();
+
+        for start in 0..haystack.len() {
+            for needle in 0..40 {
+                let slice = &haystack[start..];
+                assert_eq!(
+                    memchr(needle, slice),
+                    slice.iter().position(|&b| b == needle)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn memrchr_matches_rposition() {
+        let haystack = (0..100u8).map(|b| b % 37).collect::<Vec<_>>();
+
+        for end in 0..haystack.len() {
+            for needle in 0..40 {
+                let slice = &haystack[..end];
+                assert_eq!(
+                    memrchr(needle, slice),
+                    slice.iter().rposition(|&b| b == needle)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn count_matches_filter() {
+        let haystack = (0..100u8).map(|b| b % 7).collect::<Vec<_>>();
+
+        for start in 0..haystack.len() {
+            for needle in 0..9 {
+                let slice = &haystack[start..];
+                assert_eq!(
+                    count(needle, slice),
+                    slice.iter().filter(|&&b| b == needle).count()
+                );
+            }
+        }
+
+        assert_eq!(count(0, &[0; 64]), 64);
+        assert_eq!(count(0x80, &[0x80; 33]), 33);
+    }
+}
@@ -1,3 +1,8 @@
 pub mod container;
+pub mod distance;
 pub use container::*;
+pub mod error;
+pub mod interpolable;
+pub mod iter;
+mod memchr;
 pub mod traits;
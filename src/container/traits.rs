@@ -80,15 +80,19 @@ unsafe impl<'a, C: ?Sized + Contiguous> Contiguous for &'a mut C {
 /// The mutable version makes use of the methods implemented in [`Contiguous`].
 pub unsafe trait ContiguousMut: Contiguous {
     /// Returns a mutable pointer to the first element in the container.
+    ///
+    /// The pointer is derived from [`as_mut_slice`](ContiguousMut::as_mut_slice),
+    /// so writing through it is allowed. Casting [`begin`](Contiguous::begin) would not be,
+    /// as that pointer comes from a shared borrow.
     #[inline(always)]
     fn begin_mut(&mut self) -> *mut Self::Item {
-        self.begin() as *mut _
+        self.as_mut_slice().as_mut_ptr()
     }
 
-    /// Returns a mutable pointer to the last element in the container.
+    /// Returns a mutable pointer one past the last element in the container.
     #[inline(always)]
     fn end_mut(&mut self) -> *mut Self::Item {
-        self.end() as *mut _
+        self.as_mut_slice().as_mut_ptr_range().end
     }
 
     /// Returns the whole contiguous memory block of the container as a mutable slice.
@@ -96,6 +100,16 @@ pub unsafe trait ContiguousMut: Contiguous {
 }
 
 unsafe impl<'a, C: ?Sized + ContiguousMut> ContiguousMut for &'a mut C {
+    #[inline(always)]
+    fn begin_mut(&mut self) -> *mut Self::Item {
+        (**self).begin_mut()
+    }
+
+    #[inline(always)]
+    fn end_mut(&mut self) -> *mut Self::Item {
+        (**self).end_mut()
+    }
+
     #[inline(always)]
     fn as_mut_slice(&mut self) -> &mut [Self::Item] {
         (**self).as_mut_slice()
@@ -188,6 +202,9 @@ unsafe impl<'a, C: ?Sized + SplitUncheckedMut> SplitUncheckedMut for &'a mut C {
         (**self).split_unchecked_mut(index)
     }
 }
+/// Slices of any element type are containers.
+/// This includes slices of atomics (e.g `&[AtomicUsize]`),
+/// which can be shared across threads and updated through the shared references returned by indexing.
 unsafe impl<T> ContainerTrait for [T] {
     type Item = T;
 
@@ -205,7 +222,7 @@ unsafe impl<T> Contiguous for [T] {
 
     #[inline(always)]
     fn end(&self) -> *const Self::Item {
-        unsafe { self.begin().offset(self.len() as isize) }
+        unsafe { self.begin().add(self.len()) }
     }
 
     #[inline(always)]
@@ -275,7 +292,7 @@ unsafe impl<T> Contiguous for Vec<T> {
 
     #[inline(always)]
     fn end(&self) -> *const Self::Item {
-        unsafe { self.begin().offset(self.len() as isize) }
+        unsafe { self.begin().add(self.len()) }
     }
 
     #[inline(always)]
@@ -304,3 +321,66 @@ unsafe impl<T> GetUncheckedMut for Vec<T> {
         self.get_unchecked_mut(index)
     }
 }
+
+unsafe impl<T, const N: usize> ContainerTrait for [T; N] {
+    type Item = T;
+
+    #[inline(always)]
+    fn base_len(&self) -> usize {
+        N
+    }
+}
+
+unsafe impl<T, const N: usize> Contiguous for [T; N] {
+    #[inline(always)]
+    fn begin(&self) -> *const Self::Item {
+        self.as_ptr()
+    }
+
+    #[inline(always)]
+    fn end(&self) -> *const Self::Item {
+        unsafe { self.begin().add(N) }
+    }
+
+    #[inline(always)]
+    fn as_slice(&self) -> &[Self::Item] {
+        self
+    }
+}
+
+unsafe impl<T, const N: usize> ContiguousMut for [T; N] {
+    #[inline(always)]
+    fn as_mut_slice(&mut self) -> &mut [Self::Item] {
+        self
+    }
+}
+
+unsafe impl<T, const N: usize> GetUnchecked for [T; N] {
+    #[inline(always)]
+    unsafe fn unchecked(&self, index: usize) -> &Self::Item {
+        self.get_unchecked(index)
+    }
+}
+
+unsafe impl<T, const N: usize> GetUncheckedMut for [T; N] {
+    #[inline(always)]
+    unsafe fn unchecked_mut(&mut self, index: usize) -> &mut Self::Item {
+        self.get_unchecked_mut(index)
+    }
+}
+
+unsafe impl<T, const N: usize> SplitUnchecked for [T; N] {
+    type Split = [T];
+
+    #[inline(always)]
+    unsafe fn split_unchecked(&self, index: usize) -> (&Self::Split, &Self::Split) {
+        self[..].split_unchecked(index)
+    }
+}
+
+unsafe impl<T, const N: usize> SplitUncheckedMut for [T; N] {
+    #[inline(always)]
+    unsafe fn split_unchecked_mut(&mut self, index: usize) -> (&mut Self::Split, &mut Self::Split) {
+        self[..].split_unchecked_mut(index)
+    }
+}
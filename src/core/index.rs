@@ -1,14 +1,21 @@
 use core::{
-    cmp::Ordering,
+    cmp::{self, Ordering},
     hash::{Hash, Hasher},
     marker::PhantomData,
 };
 
 use super::{
     proof::{NonEmpty, Unknown},
+    range::{Range, RangeIter},
     seal::{Contract, Seal},
 };
 
+/// A position in between two elements of a container with contract `C`,
+/// ranging from `0` up to and including the length of the container.
+///
+/// Edges can not be used to index the container, but they can be used to split it.
+pub type Edge<C> = Index<C, Unknown>;
+
 #[allow(unused)]
 pub struct Index<C: for<'s> Contract<'s>, P = NonEmpty> {
     index: usize,
@@ -38,6 +45,22 @@ impl<C: for<'s> Contract<'s>, P> Index<C, P> {
     pub fn integer(&self) -> usize {
         self.index
     }
+
+    /// Returns the index as a plain offset, no longer tied to the contract.
+    /// Using it with the container again requires it to be vetted.
+    #[inline(always)]
+    pub fn untrusted(&self) -> usize {
+        self.index
+    }
+
+    /// Returns an iterator over all indices from `self` up to, but not including, `end`.
+    /// If `end` does not lie after `self`, the iterator is empty.
+    #[inline(always)]
+    pub fn up_to<Q>(&self, end: Index<C, Q>) -> RangeIter<C> {
+        let end = cmp::max(self.index, end.index);
+
+        unsafe { Range::<C>::from_unknown(self.index, end).into_iter() }
+    }
 }
 
 impl<C: for<'s> Contract<'s>> Index<C, NonEmpty> {
@@ -89,3 +112,26 @@ impl<C: for<'s> Contract<'s>, P> Hash for Index<C, P> {
         self.index.hash(h)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::region;
+
+    #[test]
+    fn up_to() {
+        let mut v = vec![1, 2, 3, 4, 5, 6];
+
+        region(v.as_mut_slice(), |mut s| {
+            let r = s.range().nonempty().unwrap();
+            let (lo, hi) = (r.first().after(), r.last());
+
+            for i in lo.up_to(hi) {
+                s[i] *= 10;
+            }
+
+            assert_eq!(s[..], [1, 20, 30, 40, 50, 6]);
+            assert_eq!(hi.up_to(lo).count(), 0);
+            assert_eq!(lo.up_to(lo).count(), 0);
+        });
+    }
+}
@@ -2,3 +2,4 @@ pub mod index;
 pub mod proof;
 pub mod range;
 pub mod seal;
+pub mod translator;
@@ -1,2 +1,85 @@
+use super::seal::{Contract, Seal};
+
 pub enum Unknown {}
 pub enum NonEmpty {}
+
+/// A proof about a range, that still holds for the latter part of the range after splitting it,
+/// or about an index, that holds for the range starting at that index.
+///
+/// # Safety
+/// This trait is marked unsafe,
+/// because unchecked indexing relies on the proof being carried over correctly.
+/// It is not implemented for [`ExactLen`], since the latter part of a range is shorter than the range itself.
+///
+/// ```compile_fail
+/// signed::region(vec![1, 2, 3, 4], |s| {
+///     let exact = s.range().with_exact_size::<4>().unwrap();
+///     let (_, half) = exact.split_in_half();
+/// });
+/// ```
+pub unsafe trait Transferable {}
+
+unsafe impl Transferable for Unknown {}
+unsafe impl Transferable for NonEmpty {}
+
+/// The proof that holds for the combination of a range with this proof,
+/// and an adjacent range with proof `Q`.
+/// The combination is [`NonEmpty`] if either of the ranges is.
+///
+/// # Safety
+/// This trait is marked unsafe,
+/// because unchecked indexing relies on the combined proof being correct.
+pub unsafe trait Join<Q> {
+    /// The proof of the combined range.
+    type Output;
+}
+
+unsafe impl Join<Unknown> for Unknown {
+    type Output = Unknown;
+}
+
+unsafe impl Join<NonEmpty> for Unknown {
+    type Output = NonEmpty;
+}
+
+unsafe impl Join<Unknown> for NonEmpty {
+    type Output = NonEmpty;
+}
+
+unsafe impl Join<NonEmpty> for NonEmpty {
+    type Output = NonEmpty;
+}
+
+/// A proof that a range has a length of exactly `N`.
+pub enum ExactLen<const N: usize> {}
+
+/// A proof that the container with contract `C`
+/// and the container with contract `D` are of equal length.
+///
+/// This proof can be obtained with [`crate::container::Container::len_eq`].
+#[allow(unused)]
+pub struct LenEq<C: for<'s> Contract<'s>, D: for<'s> Contract<'s>> {
+    lhs: Seal<C>,
+    rhs: Seal<D>,
+}
+
+impl<C: for<'s> Contract<'s>, D: for<'s> Contract<'s>> LenEq<C, D> {
+    /// Creates a new proof.
+    /// This function is marked unsafe,
+    /// because it can not be proved both containers are of equal length.
+    #[inline(always)]
+    pub(crate) unsafe fn new() -> Self {
+        Self {
+            lhs: Seal::new(),
+            rhs: Seal::new(),
+        }
+    }
+}
+
+impl<C: for<'s> Contract<'s>, D: for<'s> Contract<'s>> Copy for LenEq<C, D> {}
+impl<C: for<'s> Contract<'s>, D: for<'s> Contract<'s>> Clone for LenEq<C, D> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
@@ -1,11 +1,14 @@
 use core::{
+    cmp,
     hash::{Hash, Hasher},
     marker::PhantomData,
+    num::NonZeroUsize,
+    ops::{Bound, RangeBounds},
 };
 
 use super::{
     index::Index,
-    proof::{NonEmpty, Unknown},
+    proof::{ExactLen, Join, NonEmpty, Transferable, Unknown},
     seal::{Contract, Seal},
 };
 
@@ -39,6 +42,13 @@ impl<C: for<'s> Contract<'s>> Range<C> {
     /// because it can not be proved `start` and `end` make up a valid range.
     #[inline(always)]
     pub(crate) unsafe fn from_unknown(start: usize, end: usize) -> Range<C> {
+        paranoid_assert!(
+            start <= end,
+            "from_unknown: range {}..{} ends before it starts",
+            start,
+            end
+        );
+
         Range {
             start,
             end,
@@ -54,6 +64,13 @@ impl<C: for<'s> Contract<'s>> Range<C, NonEmpty> {
     /// because it can not be proved `start` and `end` make up a valid range.
     #[inline(always)]
     pub(crate) unsafe fn from_nonempty(start: usize, end: usize) -> Range<C, NonEmpty> {
+        paranoid_assert!(
+            start < end,
+            "from_nonempty: range {}..{} is empty",
+            start,
+            end
+        );
+
         Range {
             start,
             end,
@@ -69,6 +86,13 @@ impl<C: for<'s> Contract<'s>, P> Range<C, P> {
     /// because it can not be proved `start` and `end` make up a valid range.
     #[inline(always)]
     pub(crate) unsafe fn from_any(start: usize, end: usize) -> Range<C, P> {
+        paranoid_assert!(
+            start <= end,
+            "from_any: range {}..{} ends before it starts",
+            start,
+            end
+        );
+
         Range {
             start,
             end,
@@ -103,6 +127,25 @@ impl<C: for<'s> Contract<'s>, P> Hash for Range<C, P> {
     }
 }
 
+impl<C: for<'s> Contract<'s>, P> From<Range<C, P>> for core::ops::Range<usize> {
+    #[inline(always)]
+    fn from(r: Range<C, P>) -> Self {
+        r.untrusted()
+    }
+}
+
+impl<C: for<'s> Contract<'s>, P> RangeBounds<usize> for Range<C, P> {
+    #[inline(always)]
+    fn start_bound(&self) -> Bound<&usize> {
+        Bound::Included(&self.start)
+    }
+
+    #[inline(always)]
+    fn end_bound(&self) -> Bound<&usize> {
+        Bound::Excluded(&self.end)
+    }
+}
+
 impl<C: for<'s> Contract<'s>, P> Range<C, P> {
     /// Returns a new NonEmpty range.
     /// Marked unsafe, because it just assumes this is called on
@@ -124,6 +167,23 @@ impl<C: for<'s> Contract<'s>, P> Range<C, P> {
         }
     }
 
+    /// Returns the same range, with the proof of its length forgotten.
+    #[inline(always)]
+    pub fn forget_proof(&self) -> Range<C> {
+        unsafe { Range::from_unknown(self.start, self.end) }
+    }
+
+    /// Attempts to create a range with an [`ExactLen`] proof of length `N`,
+    /// returning Some if the length of the range is exactly `N`, None otherwise.
+    #[inline(always)]
+    pub fn with_exact_size<const N: usize>(&self) -> Option<Range<C, ExactLen<N>>> {
+        if self.len() == N {
+            unsafe { Some(Range::from_any(self.start, self.end)) }
+        } else {
+            None
+        }
+    }
+
     /// Returns the length of the range.
     #[inline(always)]
     pub fn len(&self) -> usize {
@@ -162,11 +222,21 @@ impl<C: for<'s> Contract<'s>, P> Range<C, P> {
         self.end
     }
 
+    /// Returns the range as plain offsets, no longer tied to the contract.
+    /// Using it with the container again requires it to be vetted.
+    #[inline(always)]
+    pub fn untrusted(&self) -> core::ops::Range<usize> {
+        self.start..self.end
+    }
+
     /// Splits the range in half,
     /// with the upper middle indexing landing in the latter half.
     /// Proof `P` of the length transfers to the latter half.
     #[inline(always)]
-    pub fn split_in_half(&self) -> (Range<C>, Range<C, P>) {
+    pub fn split_in_half(&self) -> (Range<C>, Range<C, P>)
+    where
+        P: Transferable,
+    {
         let mid = (self.end - self.start) / 2 + self.start;
 
         unsafe {
@@ -180,7 +250,10 @@ impl<C: for<'s> Contract<'s>, P> Range<C, P> {
     /// Splits the range at `index`.
     /// Proof `P` of the length transfers to the latter end.
     #[inline(always)]
-    pub fn split_index(&self, index: Index<C>) -> (Range<C>, Range<C, P>) {
+    pub fn split_index(&self, index: Index<C>) -> (Range<C>, Range<C, P>)
+    where
+        P: Transferable,
+    {
         unsafe {
             (
                 Range::from_unknown(self.start, index.integer()),
@@ -189,6 +262,90 @@ impl<C: for<'s> Contract<'s>, P> Range<C, P> {
         }
     }
 
+    /// Returns an iterator over every pair of adjacent indices within the range.
+    #[inline(always)]
+    pub fn pairs(&self) -> Pairs<C> {
+        Pairs {
+            start: self.start,
+            end: self.end,
+            contract: Seal::new(),
+        }
+    }
+
+    /// Splits the range around `index`, which is excluded from both halves.
+    /// The latter half starts directly after `index`, and may be empty.
+    ///
+    /// # Panics
+    /// Panics if `index` is not contained within the range.
+    #[inline(always)]
+    pub fn split_around(&self, index: Index<C>) -> (Range<C>, Index<C>, Range<C>) {
+        assert!(
+            self.start <= index.integer() && index.integer() < self.end,
+            "split_around: index {} is not within {}..{}",
+            index.integer(),
+            self.start,
+            self.end
+        );
+
+        unsafe {
+            (
+                Range::from_unknown(self.start, index.integer()),
+                index,
+                Range::from_unknown(index.integer() + 1, self.end),
+            )
+        }
+    }
+
+    /// Joins two adjacent ranges into the range covering both, in either order,
+    /// or returns None if there is a gap in between them or they overlap.
+    ///
+    /// The joined range is [`NonEmpty`] if either of the ranges is.
+    #[inline(always)]
+    pub fn join<Q>(self, other: Range<C, Q>) -> Option<Range<C, <P as Join<Q>>::Output>>
+    where
+        P: Join<Q>,
+    {
+        unsafe {
+            if self.end == other.start {
+                Some(Range::from_any(self.start, other.end))
+            } else if other.end == self.start {
+                Some(Range::from_any(other.start, self.end))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Returns the overlap of two ranges.
+    ///
+    /// If the ranges do not overlap, the empty range at the later of the two starts is returned.
+    #[inline(always)]
+    pub fn intersect<Q>(&self, other: &Range<C, Q>) -> Range<C> {
+        let start = cmp::max(self.start, other.start);
+        let end = cmp::max(start, cmp::min(self.end, other.end));
+
+        unsafe { Range::from_unknown(start, end) }
+    }
+
+    /// Returns the range covering both ranges if they overlap or touch,
+    /// or None if there is a gap in between them.
+    ///
+    /// The covering range is [`NonEmpty`] if either of the ranges is.
+    #[inline(always)]
+    pub fn union<Q>(&self, other: &Range<C, Q>) -> Option<Range<C, <P as Join<Q>>::Output>>
+    where
+        P: Join<Q>,
+    {
+        if cmp::max(self.start, other.start) > cmp::min(self.end, other.end) {
+            return None;
+        }
+
+        let start = cmp::min(self.start, other.start);
+        let end = cmp::max(self.end, other.end);
+
+        unsafe { Some(Range::from_any(start, end)) }
+    }
+
     /// Returns Some if `index` is contained within the range.
     #[inline(always)]
     pub fn contains(&self, index: usize) -> Option<Index<C, P>> {
@@ -203,6 +360,12 @@ impl<C: for<'s> Contract<'s>, P> Range<C, P> {
 }
 
 impl<C: for<'s> Contract<'s>> Range<C, NonEmpty> {
+    /// Returns the length of the range, which is proven to be non-zero.
+    #[inline(always)]
+    pub fn len_nonzero(&self) -> NonZeroUsize {
+        unsafe { NonZeroUsize::new_unchecked(self.end - self.start) }
+    }
+
     /// Returns the last index of the range.
     #[inline(always)]
     pub fn last(&self) -> Index<C> {
@@ -252,6 +415,28 @@ impl<C: for<'s> Contract<'s>> Range<C, NonEmpty> {
             false
         }
     }
+
+    /// Returns an index within the range, chosen uniformly at random by `rng`.
+    #[cfg(feature = "algorithms")]
+    #[inline]
+    pub fn random_index<R: crate::algorithms::Rng + ?Sized>(&self, rng: &mut R) -> Index<C> {
+        let len = self.len() as u64;
+
+        // Lemire's multiply-and-reject: the high half of `x * len` is uniform in `0..len`,
+        // once the few values of `x` whose low half falls below `2^64 % len` are rejected.
+        let mut product = u128::from(rng.next_u64()) * u128::from(len);
+
+        if (product as u64) < len {
+            let threshold = len.wrapping_neg() % len;
+
+            while (product as u64) < threshold {
+                product = u128::from(rng.next_u64()) * u128::from(len);
+            }
+        }
+
+        // The offset is less than the length of the range.
+        unsafe { Index::new(self.start + (product >> 64) as usize) }
+    }
 }
 
 impl<C: for<'s> Contract<'s>, P> IntoIterator for Range<C, P> {
@@ -297,6 +482,12 @@ impl<C: for<'s> Contract<'s>> Iterator for RangeIter<C> {
             None
         }
     }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end.saturating_sub(self.start);
+        (len, Some(len))
+    }
 }
 
 impl<C: for<'s> Contract<'s>> DoubleEndedIterator for RangeIter<C> {
@@ -318,6 +509,43 @@ impl<C: for<'s> Contract<'s>> core::iter::ExactSizeIterator for RangeIter<C> {
     }
 }
 
+/// An Iterator over the pairs of adjacent indices within any Range with contract `C`.
+#[allow(unused)]
+pub struct Pairs<C: for<'s> Contract<'s>> {
+    start: usize,
+    end: usize,
+    contract: Seal<C>,
+}
+
+impl<C: for<'s> Contract<'s>> Copy for Pairs<C> {}
+impl<C: for<'s> Contract<'s>> Clone for Pairs<C> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: for<'s> Contract<'s>> Iterator for Pairs<C> {
+    type Item = (Index<C>, Index<C>);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.end - self.start > 1 {
+            let idx = self.start;
+            self.start += 1;
+            unsafe { Some((Index::new(idx), Index::new(idx + 1))) }
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end.saturating_sub(self.start).saturating_sub(1);
+        (len, Some(len))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Range;
@@ -328,4 +556,204 @@ mod tests {
         let range: Range<Signed> = unsafe { Range::from_unknown(0, 10) };
         assert_eq!(range.len(), 10);
     }
+
+    #[test]
+    fn split_around() {
+        use crate::core::{index::Index, seal::Signed};
+        let range: Range<Signed> = unsafe { Range::from_unknown(2, 6) };
+
+        let (lhs, index, rhs) = range.split_around(unsafe { Index::new(5) });
+        assert!(lhs.start() == 2 && lhs.end() == 5);
+        assert_eq!(index.integer(), 5);
+        assert!(rhs.start() == 6 && rhs.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "split_around: index 6 is not within 2..6")]
+    fn split_around_outside() {
+        use crate::core::{index::Index, seal::Signed};
+        let range: Range<Signed> = unsafe { Range::from_unknown(2, 6) };
+        range.split_around(unsafe { Index::new(6) });
+    }
+
+    #[test]
+    fn len_nonzero() {
+        use crate::core::seal::Signed;
+        let range: Range<Signed> = unsafe { Range::from_unknown(2, 5) };
+        assert_eq!(range.nonempty().unwrap().len_nonzero().get(), 3);
+    }
+
+    #[test]
+    fn pairs() {
+        use crate::core::seal::Signed;
+        let range: Range<Signed> = unsafe { Range::from_unknown(3, 6) };
+        let pairs = range
+            .pairs()
+            .map(|(a, b)| (a.integer(), b.integer()))
+            .collect::<Vec<_>>();
+        assert_eq!(pairs, [(3, 4), (4, 5)]);
+
+        let single: Range<Signed> = unsafe { Range::from_unknown(3, 4) };
+        assert_eq!(single.pairs().count(), 0);
+
+        let empty: Range<Signed> = unsafe { Range::from_unknown(3, 3) };
+        assert_eq!(empty.pairs().count(), 0);
+    }
+
+    #[test]
+    fn untrusted() {
+        use crate::region;
+
+        let stored = region(vec![1, 2, 3, 4], |s| {
+            let r = s.vet_range(1, 3).unwrap();
+            assert_eq!(r.first().untrusted(), 1);
+            assert_eq!(core::ops::Range::from(r), 1..3);
+
+            r.untrusted()
+        });
+
+        assert_eq!(stored, 1..3);
+
+        region(vec![5, 6, 7, 8], |s| {
+            let r = s.vet_range(stored.start, stored.end).unwrap();
+            assert_eq!(s[r], [6, 7]);
+        });
+    }
+
+    #[test]
+    fn range_bounds() {
+        use crate::region;
+
+        let mut v = vec![1, 2, 3, 4, 5];
+        let snapshot = v.clone();
+
+        region(snapshot.as_slice(), |s| {
+            let start = s.index_of(&2).unwrap();
+            let end = s.index_of(&5).unwrap();
+            let r = s.vet_range(start.integer(), end.integer()).unwrap();
+
+            let drained: Vec<_> = v.drain(r).collect();
+            assert_eq!(drained, [2, 3, 4]);
+        });
+
+        assert_eq!(v, [1, 5]);
+    }
+
+    #[test]
+    fn join() {
+        use crate::region;
+
+        region(vec![1, 2, 3, 4, 5], |s| {
+            let r = s.range().nonempty().unwrap();
+            let (lhs, rhs) = r.split_in_half();
+
+            let whole = lhs.join(rhs).unwrap();
+            assert!(whole == r);
+            assert_eq!(s[whole.first()], 1);
+
+            assert!(rhs.join(lhs).unwrap() == r);
+
+            let (a, b) = (s.vet_range(0, 1).unwrap(), s.vet_range(2, 3).unwrap());
+            assert!(a.join(b).is_none());
+            assert!(lhs.join(r).is_none());
+
+            let empty = s.vet_range(2, 2).unwrap();
+            let joined = empty.join(empty).unwrap();
+            assert!(joined.is_empty() && joined.start() == 2);
+        });
+    }
+
+    #[test]
+    fn intersect() {
+        use crate::region;
+
+        region(vec![0; 10], |s| {
+            let window = s.vet_range(2, 6).unwrap();
+            let bounds = |r: super::Range<_>| (r.start(), r.end());
+
+            // Overlapping from either side.
+            assert_eq!(
+                bounds(window.intersect(&s.vet_range(4, 9).unwrap())),
+                (4, 6)
+            );
+            assert_eq!(
+                bounds(window.intersect(&s.vet_range(0, 3).unwrap())),
+                (2, 3)
+            );
+
+            // One containing the other.
+            assert_eq!(bounds(window.intersect(&s.range())), (2, 6));
+            assert_eq!(bounds(s.range().intersect(&window)), (2, 6));
+
+            // Touching, but not overlapping.
+            assert_eq!(
+                bounds(window.intersect(&s.vet_range(6, 8).unwrap())),
+                (6, 6)
+            );
+            assert_eq!(
+                bounds(window.intersect(&s.vet_range(0, 2).unwrap())),
+                (2, 2)
+            );
+
+            // Disjoint.
+            assert_eq!(
+                bounds(window.intersect(&s.vet_range(8, 10).unwrap())),
+                (8, 8)
+            );
+            assert_eq!(
+                bounds(s.vet_range(8, 10).unwrap().intersect(&window)),
+                (8, 8)
+            );
+
+            let clipped = window.intersect(&s.vet_range(5, 10).unwrap());
+            assert_eq!(s[clipped].len(), 1);
+        });
+    }
+
+    #[test]
+    fn union() {
+        use crate::region;
+
+        region(vec![0; 10], |s| {
+            let dirty = s.vet_range_nonempty(2, 5).unwrap();
+            let bounds = |r: Option<super::Range<_, _>>| r.map(|r| (r.start(), r.end()));
+
+            assert_eq!(
+                bounds(dirty.union(&s.vet_range(4, 8).unwrap())),
+                Some((2, 8))
+            );
+            assert_eq!(
+                bounds(dirty.union(&s.vet_range(5, 7).unwrap())),
+                Some((2, 7))
+            );
+            assert_eq!(
+                bounds(dirty.union(&s.vet_range(0, 2).unwrap())),
+                Some((0, 5))
+            );
+            assert_eq!(bounds(dirty.union(&s.range())), Some((0, 10)));
+            assert_eq!(bounds(dirty.union(&s.vet_range(6, 8).unwrap())), None);
+            assert_eq!(bounds(s.vet_range(0, 1).unwrap().union(&dirty)), None);
+
+            // The union with a NonEmpty range is NonEmpty.
+            let merged = s.vet_range(3, 3).unwrap().union(&dirty).unwrap();
+            assert_eq!(s[merged.first()], 0);
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "paranoid")]
+    #[should_panic(expected = "from_unknown: range 5..4 ends before it starts")]
+    fn paranoid_inverted() {
+        use crate::core::seal::Signed;
+        let _: Range<Signed> = unsafe { Range::from_unknown(5, 4) };
+    }
+
+    #[test]
+    #[cfg(feature = "paranoid")]
+    #[should_panic(expected = "from_nonempty: range 3..3 is empty")]
+    fn paranoid_empty_nonempty() {
+        use crate::core::seal::Signed;
+        let range: Range<Signed> = unsafe { Range::from_unknown(3, 3) };
+        let _ = unsafe { range.assume_nonempty() };
+    }
 }
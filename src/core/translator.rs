@@ -0,0 +1,102 @@
+use super::{
+    index::Index,
+    seal::{Contract, Seal},
+};
+
+/// Translates indices of a container with contract `C`,
+/// into indices of the two containers it was split into, with contracts `L` and `R`, and back.
+///
+/// A translator is handed out by [`crate::container::Container::split_at_with`]
+/// and [`crate::container::Container::split_at_mut_with`].
+#[allow(unused)]
+pub struct Translator<C, L, R>
+where
+    C: for<'s> Contract<'s>,
+    L: for<'s> Contract<'s>,
+    R: for<'s> Contract<'s>,
+{
+    mid: usize,
+    parent: Seal<C>,
+    left: Seal<L>,
+    right: Seal<R>,
+}
+
+impl<C, L, R> Translator<C, L, R>
+where
+    C: for<'s> Contract<'s>,
+    L: for<'s> Contract<'s>,
+    R: for<'s> Contract<'s>,
+{
+    /// Creates a new translator, for a container split at `mid`.
+    /// This function is marked unsafe,
+    /// because it can not be proved `L` and `R` are the contracts of the halves before and after `mid`.
+    #[inline(always)]
+    pub(crate) unsafe fn new(mid: usize) -> Self {
+        Self {
+            mid,
+            parent: Seal::new(),
+            left: Seal::new(),
+            right: Seal::new(),
+        }
+    }
+
+    /// Returns the index at which the container was split.
+    #[inline(always)]
+    pub fn mid(&self) -> usize {
+        self.mid
+    }
+
+    /// Translates `index` into an index of the left half,
+    /// returning None if `index` lies in the right half.
+    #[inline(always)]
+    pub fn left(&self, index: Index<C>) -> Option<Index<L>> {
+        if index.integer() < self.mid {
+            unsafe { Some(Index::new(index.integer())) }
+        } else {
+            None
+        }
+    }
+
+    /// Translates `index` into an index of the right half,
+    /// returning None if `index` lies in the left half.
+    #[inline(always)]
+    pub fn right(&self, index: Index<C>) -> Option<Index<R>> {
+        if index.integer() >= self.mid {
+            unsafe { Some(Index::new(index.integer() - self.mid)) }
+        } else {
+            None
+        }
+    }
+
+    /// Translates an index of the left half back into an index of the container.
+    #[inline(always)]
+    pub fn from_left(&self, index: Index<L>) -> Index<C> {
+        unsafe { Index::new(index.integer()) }
+    }
+
+    /// Translates an index of the right half back into an index of the container.
+    #[inline(always)]
+    pub fn from_right(&self, index: Index<R>) -> Index<C> {
+        unsafe { Index::new(index.integer() + self.mid) }
+    }
+}
+
+impl<C, L, R> Copy for Translator<C, L, R>
+where
+    C: for<'s> Contract<'s>,
+    L: for<'s> Contract<'s>,
+    R: for<'s> Contract<'s>,
+{
+}
+
+impl<C, L, R> Clone for Translator<C, L, R>
+where
+    C: for<'s> Contract<'s>,
+    L: for<'s> Contract<'s>,
+    R: for<'s> Contract<'s>,
+{
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
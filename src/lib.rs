@@ -1,10 +1,69 @@
 #![feature(nll)]
+#![feature(min_specialization)]
+#[macro_use]
+mod macros;
+#[cfg(feature = "algorithms")]
+pub mod algorithms;
+#[cfg(feature = "paranoid")]
+pub mod checked;
 pub mod container;
 pub mod core;
+#[cfg(test)]
+mod test_util;
+
+/// Derives the container traits for a struct with a single field,
+/// forwarding every trait the field implements.
+///
+/// ```
+/// use signed::{region, Container};
+///
+/// #[derive(Container)]
+/// struct Samples(Vec<f32>);
+///
+/// region(Samples(vec![1.0, 2.0, 3.0]), |s| {
+///     assert_eq!(s[s.range()], [1.0, 2.0, 3.0]);
+/// });
+/// ```
+///
+/// Structs with more than one field, and enums, are rejected:
+///
+/// ```compile_fail
+/// #[derive(signed::Container)]
+/// struct Pair(Vec<u8>, Vec<u8>);
+/// ```
+///
+/// ```compile_fail
+/// #[derive(signed::Container)]
+/// enum Either {
+///     Left(Vec<u8>),
+///     Right(Vec<u8>),
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use signed_derive::Container;
 
 use crate::core::seal::Signed;
 use container::{traits::ContainerTrait, Container};
 
+/// Calls `f` with `container` wrapped in a [`Container`] with a contract of its own.
+///
+/// ```
+/// use signed::region;
+///
+/// let mut v = vec![1, 2, 3];
+///
+/// region(v.as_mut_slice(), |mut s| {
+///     for x in &mut s {
+///         *x *= 2;
+///     }
+///
+///     let mut sum = 0;
+///     for x in &s {
+///         sum += x;
+///     }
+///     assert_eq!(sum, 12);
+/// });
+/// ```
 pub fn region<C, F, Out>(container: C, f: F) -> Out
 where
     F: for<'id> FnOnce(Container<Signed<'id>, C>) -> Out,
@@ -55,6 +114,97 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_scan_from_mut() {
+        let mut v = vec![1, 2, 3, 4, 5, 6, 7];
+
+        region(v.as_mut_slice(), |mut s| {
+            if let Some(r) = s.range().nonempty() {
+                let mid = r.upper_middle();
+                assert_eq!(mid.integer(), 3);
+                let scanned_range = s.scan_from_mut(mid, |x| {
+                    if *x < 6 {
+                        *x *= 10;
+                        true
+                    } else {
+                        false
+                    }
+                });
+
+                assert_eq!(s[scanned_range], [4, 50]);
+            }
+        });
+
+        assert_eq!(v, [1, 2, 3, 4, 50, 6, 7]);
+    }
+
+    #[test]
+    fn test_scan_after_before() {
+        let mut v = b"key=value".to_vec();
+
+        region(v.as_mut_slice(), |s| {
+            let separator = s.index_of(&b'=').unwrap();
+
+            let value = s.scan_after(separator, |b| b.is_ascii_alphabetic());
+            let key = s.scan_before(separator, |b| b.is_ascii_alphabetic());
+
+            assert_eq!(s[key], *b"key");
+            assert_eq!(s[value], *b"value");
+
+            let nothing = s.scan_after(separator, |b| b.is_ascii_digit());
+            assert!(nothing.is_empty() && nothing.start() == 4);
+
+            let nothing = s.scan_before(separator, |b| b.is_ascii_digit());
+            assert!(nothing.is_empty() && nothing.end() == 3);
+        })
+    }
+
+    #[test]
+    fn test_scan_range() {
+        // Two records, which a scan must never cross.
+        let mut v = vec![1, 1, 1, 1, 2, 1];
+
+        region(v.as_mut_slice(), |s| {
+            let first = s.vet_range(0, 3).unwrap();
+            let second = s.vet_range(3, 6).unwrap();
+
+            let ones = s.scan_range(first, |x| *x == 1);
+            assert!(ones == first);
+
+            let ones = s.scan_range(second, |x| *x == 1);
+            assert!(ones.start() == 3 && ones.end() == 4);
+
+            let none = s.scan_range(second, |x| *x == 2);
+            assert!(none.is_empty() && none.start() == 3);
+
+            let empty = s.vet_range(2, 2).unwrap();
+            assert!(s.scan_range(empty, |_| true).is_empty());
+        })
+    }
+
+    #[test]
+    fn test_run_at() {
+        let mut v = vec![1, 1, 2, 2, 2, 3, 3];
+
+        region(v.as_mut_slice(), |s| {
+            let run = s.run_at(s.vet(3).unwrap(), |a, b| a == b);
+            assert!(run.start() == 2 && run.end() == 5);
+
+            // Runs starting at the first, and ending at the last element.
+            let first = s.run_at(s.vet(1).unwrap(), |a, b| a == b);
+            assert!(first.start() == 0 && first.end() == 2);
+
+            let last = s.run_at(s.vet(5).unwrap(), |a, b| a == b);
+            assert!(last.start() == 5 && last.end() == 7);
+
+            let single = s.run_at(s.vet(3).unwrap(), |_, _| false);
+            assert!(single.start() == 3 && single.end() == 4);
+
+            let all = s.run_at(s.vet(0).unwrap(), |_, _| true);
+            assert!(all == s.range());
+        })
+    }
+
     #[test]
     fn test_split_first() {
         let mut v = vec![1, 2, 3, 4, 5, 6, 7];
@@ -101,6 +251,52 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_split_last() {
+        let mut v = vec![1, 2, 3, 4, 5, 6, 7];
+
+        region(v.as_mut_slice(), |mut s| {
+            let (last, rest) = s.split_last().unwrap();
+
+            assert_eq!(last, &7);
+            assert_eq!(rest[..], [1, 2, 3, 4, 5, 6]);
+
+            let (last, rest) = s.split_last_mut().unwrap();
+
+            assert_eq!(last, &mut 7);
+            assert_eq!(rest[..], [1, 2, 3, 4, 5, 6]);
+        })
+    }
+
+    #[test]
+    fn test_split_last_size_is_1() {
+        let mut v = vec![1];
+
+        region(v.as_mut_slice(), |mut s| {
+            let (last, rest) = s.split_last().unwrap();
+
+            assert_eq!(last, &1);
+            assert_eq!(rest[..], []);
+            assert!(rest.is_empty());
+
+            let (last, rest) = s.split_last_mut().unwrap();
+
+            assert_eq!(last, &mut 1);
+            assert_eq!(rest[..], []);
+            assert!(rest.is_empty());
+        })
+    }
+
+    #[test]
+    fn test_split_last_size_is_0() {
+        let mut v: Vec<usize> = vec![];
+
+        region(v.as_mut_slice(), |mut s| {
+            assert!(s.split_last().is_none());
+            assert!(s.split_last_mut().is_none());
+        })
+    }
+
     #[test]
     fn test_split_index_into_other() {
         let mut v = vec![1, 2, 3, 4, 5, 6];
@@ -0,0 +1,10 @@
+/// Asserts a condition with the `paranoid` feature enabled,
+/// and expands to nothing without it.
+///
+/// Used to validate the invariants unsafe code relies on, at the points where they are minted.
+macro_rules! paranoid_assert {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "paranoid")]
+        assert!($($arg)*);
+    };
+}
@@ -0,0 +1,31 @@
+//! Helpers shared by the unit tests.
+
+/// Advances `state` with a xorshift generator, and returns the new state.
+///
+/// `state` must not be zero, as zero maps to itself.
+pub(crate) fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+/// Every permutation of `items`, in lexicographic order if `items` is sorted.
+#[cfg(feature = "algorithms")]
+pub(crate) fn permutations<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    if items.is_empty() {
+        return vec![Vec::new()];
+    }
+
+    (0..items.len())
+        .flat_map(|i| {
+            let mut rest = items.to_vec();
+            let first = rest.remove(i);
+
+            permutations(&rest).into_iter().map(move |mut tail| {
+                tail.insert(0, first.clone());
+                tail
+            })
+        })
+        .collect()
+}
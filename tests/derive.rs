@@ -0,0 +1,88 @@
+#![cfg(feature = "derive")]
+
+use signed::{region, Container};
+
+#[derive(Container)]
+struct Samples(Vec<f32>);
+
+#[derive(Container)]
+pub struct Packet {
+    pub bytes: [u8; 8],
+}
+
+#[derive(Container)]
+struct Buffer<'a, T>(&'a mut [T])
+where
+    T: Copy;
+
+#[derive(Container)]
+pub struct Callbacks<F: Fn() -> u8>(Vec<F>);
+
+#[test]
+fn derive_vec_newtype() {
+    let samples = Samples(vec![1.0, 2.0, 3.0, 4.0]);
+
+    region(samples, |mut s| {
+        if let Some(r) = s.range().nonempty() {
+            let mid = r.upper_middle();
+            s[mid] = 10.0;
+
+            assert_eq!(s[..mid], [1.0, 2.0]);
+            assert_eq!(s[mid..], [10.0, 4.0]);
+        }
+    });
+}
+
+#[test]
+fn derive_array_newtype() {
+    let mut packet = Packet {
+        bytes: [1, 2, 3, 4, 5, 6, 7, 8],
+    };
+
+    region(&mut packet, |mut s| {
+        let (first, rest) = s.split_first().unwrap();
+        assert_eq!(first, &1);
+        assert_eq!(rest, [2, 3, 4, 5, 6, 7, 8]);
+
+        let mid = s.range().nonempty().unwrap().upper_middle();
+        let (lhs, rhs) = s.split_at_mut(mid);
+
+        region(lhs, |lhs| {
+            assert_eq!(lhs[lhs.range()], [1, 2, 3, 4]);
+        });
+
+        region(rhs, |rhs| {
+            assert_eq!(rhs[rhs.range()], [5, 6, 7, 8]);
+        });
+    });
+}
+
+#[test]
+fn derive_generic_newtype() {
+    let mut v = vec![1, 2, 3, 4, 5, 6];
+
+    region(Buffer(v.as_mut_slice()), |mut s| {
+        let mid = s.range().nonempty().unwrap().upper_middle();
+        let (lhs, rhs) = s.split_at(mid);
+
+        assert_eq!(lhs, [1, 2, 3]);
+        assert_eq!(rhs, [4, 5, 6]);
+
+        let r = s.range().nonempty().unwrap();
+        s.swap(r.first(), r.last());
+    });
+
+    assert_eq!(v, [6, 2, 3, 4, 5, 1]);
+}
+
+#[test]
+fn derive_fn_bound() {
+    let callbacks = Callbacks(vec![|| 1, || 2, || 3]);
+
+    region(callbacks, |s| {
+        let r = s.range().nonempty().unwrap();
+
+        assert_eq!((s[r.first()])(), 1);
+        assert_eq!((s[r.last()])(), 3);
+    });
+}